@@ -0,0 +1,47 @@
+// VELOX CORE - Entry Id Benchmark
+// Cost of giving every scanned entry an id: the UUIDs scans used to make,
+// the counter `generateIds` uses now, and the empty default
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Entries per iteration; set `VELOX_BENCH_ENTRIES` for a quicker run
+const DEFAULT_ENTRIES: usize = 1_000_000;
+
+static NEXT_ENTRY_ID: AtomicU64 = AtomicU64::new(1);
+
+fn bench_entry_ids(c: &mut Criterion) {
+    let entries = std::env::var("VELOX_BENCH_ENTRIES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_ENTRIES);
+
+    let mut group = c.benchmark_group(format!("entry_ids_{}", entries));
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(20));
+    // Dropping a million strings is part of what a scan pays, so it stays timed
+    group.bench_function("uuid_v4", |b| {
+        b.iter(|| {
+            (0..entries)
+                .map(|_| uuid::Uuid::new_v4().to_string())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("counter", |b| {
+        b.iter(|| {
+            (0..entries)
+                .map(|_| NEXT_ENTRY_ID.fetch_add(1, Ordering::Relaxed).to_string())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("empty", |b| {
+        b.iter(|| (0..entries).map(|_| String::new()).collect::<Vec<_>>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_entry_ids);
+criterion_main!(benches);
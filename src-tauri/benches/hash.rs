@@ -0,0 +1,74 @@
+// VELOX CORE - Hash Benchmark
+// SHA-256 against BLAKE3 throughput on one large file, streamed in the
+// same 64KB chunks the scanner uses
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use sha2::{Digest, Sha256};
+
+/// File size in MiB; set `VELOX_BENCH_HASH_MB` for a quicker run
+const DEFAULT_MB: u64 = 1024;
+const CHUNK_SIZE: usize = 64 * 1024;
+
+fn write_file(path: &Path, megabytes: u64) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    // Varied bytes so no layer below the hasher can shortcut zero pages
+    let block: Vec<u8> = (0..1024 * 1024).map(|i| (i * 31 % 251) as u8).collect();
+    for _ in 0..megabytes {
+        file.write_all(&block)?;
+    }
+    file.sync_all()
+}
+
+fn stream_file(path: &Path, mut update: impl FnMut(&[u8])) {
+    let mut file = File::open(path).expect("bench file");
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer).expect("read bench file");
+        if read == 0 {
+            return;
+        }
+        update(&buffer[..read]);
+    }
+}
+
+fn sha256(path: &Path) -> String {
+    let mut hasher = Sha256::new();
+    stream_file(path, |chunk| hasher.update(chunk));
+    format!("{:x}", hasher.finalize())
+}
+
+fn blake3(path: &Path) -> String {
+    let mut hasher = blake3::Hasher::new();
+    stream_file(path, |chunk| {
+        hasher.update(chunk);
+    });
+    hasher.finalize().to_hex().to_string()
+}
+
+fn bench_hash(c: &mut Criterion) {
+    let megabytes = std::env::var("VELOX_BENCH_HASH_MB")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MB);
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("data.bin");
+    write_file(&path, megabytes).expect("bench file");
+
+    let mut group = c.benchmark_group(format!("hash_{}_mib", megabytes));
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(60))
+        .throughput(Throughput::Bytes(megabytes * 1024 * 1024));
+    group.bench_function("sha256", |b| b.iter(|| sha256(&path)));
+    group.bench_function("blake3", |b| b.iter(|| blake3(&path)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_hash);
+criterion_main!(benches);
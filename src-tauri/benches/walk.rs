@@ -0,0 +1,87 @@
+// VELOX CORE - Walk Benchmark
+// Sequential walkdir against parallel jwalk over a generated tree, both
+// reading metadata for every entry the way a scan does
+
+use std::fs::{self, File, Metadata};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use jwalk::{ClientState, Parallelism, WalkDirGeneric};
+use walkdir::WalkDir;
+
+/// Files in the generated tree; set `VELOX_BENCH_FILES` for a quicker run
+const DEFAULT_FILES: usize = 500_000;
+const FILES_PER_DIR: usize = 1_000;
+
+#[derive(Debug, Default)]
+struct StatState;
+
+impl ClientState for StatState {
+    type ReadDirState = ();
+    type DirEntryState = Option<io::Result<Metadata>>;
+}
+
+/// root/dNNNN/fNNNN, empty files so only the walk itself is measured
+fn build_tree(root: &Path, files: usize) -> io::Result<()> {
+    for index in 0..files {
+        let dir = root.join(format!("d{:04}", index / FILES_PER_DIR));
+        if index % FILES_PER_DIR == 0 {
+            fs::create_dir(&dir)?;
+        }
+        File::create(dir.join(format!("f{:04}", index % FILES_PER_DIR)))?;
+    }
+    Ok(())
+}
+
+fn walk_sequential(root: &Path) -> usize {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.metadata().is_ok())
+        .count()
+}
+
+fn walk_parallel(root: &Path, threads: usize) -> usize {
+    WalkDirGeneric::<StatState>::new(root)
+        .skip_hidden(false)
+        .parallelism(Parallelism::RayonNewPool(threads))
+        .process_read_dir(|_, _, _, children| {
+            for child in children.iter_mut().flatten() {
+                child.client_state = Some(fs::symlink_metadata(child.path()));
+            }
+        })
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| !matches!(entry.client_state, Some(Err(_))))
+        .count()
+}
+
+fn bench_walk(c: &mut Criterion) {
+    let files = std::env::var("VELOX_BENCH_FILES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_FILES);
+    let dir = tempfile::tempdir().expect("temp dir");
+    build_tree(dir.path(), files).expect("bench tree");
+    let threads = num_cpus::get().max(1);
+
+    // Both walkers must see the same tree: files, their directories, and the root
+    let expected = files + (files + FILES_PER_DIR - 1) / FILES_PER_DIR + 1;
+    assert_eq!(walk_sequential(dir.path()), expected);
+    assert_eq!(walk_parallel(dir.path(), threads), expected);
+
+    let mut group = c.benchmark_group(format!("walk_{}_files", files));
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(30));
+    group.bench_function("walkdir", |b| b.iter(|| walk_sequential(dir.path())));
+    group.bench_function(format!("jwalk_{}_threads", threads), |b| {
+        b.iter(|| walk_parallel(dir.path(), threads))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_walk);
+criterion_main!(benches);
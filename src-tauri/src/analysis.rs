@@ -0,0 +1,824 @@
+// VELOX CORE - Scan Analysis
+// Post-processing over collected scan entries
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use img_hash::{HasherConfig, ImageHash};
+use tokio::task::JoinHandle;
+
+use crate::classify::{classify_extension, file_extension};
+use crate::error::{VeloxError, VeloxResult};
+use crate::hasher::digest_file_with_retry;
+use crate::types::{
+    format_bytes, AgeBucket, ByteUnit, CategorySummary, DepthBucket, DuplicateGroup,
+    DuplicateReport, EntryField, EntryPage, ExtensionStat, FileCategory, FileEntry, HashAlgorithm,
+    QueryParams, ScanDiff, ScanResult, ScanSession, SimilarGroup, SimilarImage, TreeNode,
+};
+
+/// Group regular files with identical content.
+///
+/// Files are bucketed by size first and only same-size candidates are hashed,
+/// so unique-size files are never read. Empty files are ignored and groups with
+/// a single member are dropped. Groups are ordered by wasted bytes, largest first.
+/// Transient read failures are retried up to `io_retries` times.
+pub async fn find_duplicates(
+    entries: &[FileEntry],
+    session: &ScanSession,
+    algorithm: HashAlgorithm,
+    io_retries: u8,
+    byte_unit: ByteUnit,
+) -> VeloxResult<DuplicateReport> {
+    let mut by_size: HashMap<u64, Vec<&FileEntry>> = HashMap::new();
+    for entry in entries.iter().filter(|e| e.is_file && e.size > 0) {
+        by_size.entry(entry.size).or_default().push(entry);
+    }
+
+    let mut groups = Vec::new();
+
+    for (size, candidates) in by_size.into_iter().filter(|(_, c)| c.len() > 1) {
+        let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+
+        for candidate in candidates {
+            if session.is_cancelled() {
+                return Err(VeloxError::ScanCancelled);
+            }
+            // Hashing runs after the walk, so keep the idle reaper away
+            session.touch();
+
+            // Reuse a digest from the scan when one was already computed
+            let digest = match &candidate.hash {
+                Some(hash) => hash.clone(),
+                None => match digest_file_with_retry(Path::new(&candidate.path), algorithm, io_retries)
+                    .await
+                {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        tracing::warn!("⚠️ Failed to hash {}: {}", candidate.path, e);
+                        continue;
+                    }
+                },
+            };
+
+            by_hash.entry(digest).or_default().push(candidate.path.clone());
+        }
+
+        groups.extend(
+            by_hash
+                .into_iter()
+                .filter(|(_, paths)| paths.len() > 1)
+                .map(|(hash, paths)| DuplicateGroup {
+                    hash,
+                    size,
+                    size_formatted: format_bytes(size, byte_unit),
+                    paths,
+                }),
+        );
+    }
+
+    groups.sort_by(|a, b| {
+        let wasted = |g: &DuplicateGroup| g.size * (g.paths.len() as u64 - 1);
+        wasted(b).cmp(&wasted(a))
+    });
+
+    tracing::info!("🧬 Found {} duplicate groups", groups.len());
+
+    Ok(DuplicateReport { groups })
+}
+
+/// Perceptual hash of an image. Unlike a content digest it barely changes
+/// when the picture is resized, recompressed or slightly edited.
+fn perceptual_hash(path: &Path) -> VeloxResult<ImageHash> {
+    let image = image::open(path).map_err(|e| VeloxError::IoAt {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    Ok(HasherConfig::new().to_hasher().hash_image(&image))
+}
+
+/// Group images that look alike, e.g. resized or re-encoded copies of a photo.
+///
+/// Only files categorized as images are decoded. Each image joins the first
+/// group whose first image's perceptual hash is within `threshold` bits
+/// (Hamming distance) of its own, or starts a new group. Groups with a single
+/// image are dropped and the largest groups come first. Images that fail to
+/// decode are skipped. Images are decoded on the blocking pool, up to one per
+/// CPU at a time. `on_image` is called after each image, in order, with the
+/// number processed so far, the image count and the image.
+pub async fn find_similar_images(
+    entries: &[FileEntry],
+    session: &ScanSession,
+    threshold: u32,
+    mut on_image: impl FnMut(usize, usize, &FileEntry),
+) -> VeloxResult<Vec<SimilarGroup>> {
+    let images: Vec<&FileEntry> = entries
+        .iter()
+        .filter(|e| e.is_file && e.category == FileCategory::Image)
+        .collect();
+
+    // Decodes run on the blocking pool, a few ahead of the one being grouped.
+    // Results are still taken in order, so grouping stays deterministic.
+    let parallelism = num_cpus::get().max(1);
+    let mut decoding: VecDeque<JoinHandle<VeloxResult<ImageHash>>> = VecDeque::new();
+    let mut queued = images.iter();
+
+    let mut groups: Vec<(ImageHash, Vec<SimilarImage>)> = Vec::new();
+    for (index, entry) in images.iter().enumerate() {
+        if session.is_cancelled() {
+            return Err(VeloxError::ScanCancelled);
+        }
+        // Decoding runs after the walk, so keep the idle reaper away
+        session.touch();
+
+        while decoding.len() < parallelism {
+            let Some(next) = queued.next() else {
+                break;
+            };
+            let path = PathBuf::from(&next.path);
+            decoding.push_back(tokio::task::spawn_blocking(move || perceptual_hash(&path)));
+        }
+
+        let hashed = match decoding.pop_front() {
+            Some(task) => task.await.unwrap_or_else(|e| {
+                Err(VeloxError::Unknown(format!("Blocking task failed: {}", e)))
+            }),
+            None => break,
+        };
+        on_image(index + 1, images.len(), entry);
+
+        let hash = match hashed {
+            Ok(hash) => hash,
+            Err(e) => {
+                tracing::warn!("⚠️ Failed to fingerprint {}: {}", entry.path, e);
+                continue;
+            }
+        };
+
+        let closest = groups
+            .iter_mut()
+            .map(|(first, images)| (first.dist(&hash), images))
+            .find(|(distance, _)| *distance <= threshold);
+        let image = |distance| SimilarImage {
+            path: entry.path.clone(),
+            size: entry.size,
+            distance,
+        };
+        match closest {
+            Some((distance, images)) => images.push(image(distance)),
+            None => groups.push((hash, vec![image(0)])),
+        }
+    }
+
+    let mut groups: Vec<SimilarGroup> = groups
+        .into_iter()
+        .filter(|(_, images)| images.len() > 1)
+        .map(|(hash, mut images)| {
+            images.sort_by_key(|image| image.distance);
+            SimilarGroup {
+                hash: hash.to_base64(),
+                images,
+            }
+        })
+        .collect();
+    groups.sort_by_key(|group| Reverse(group.images.len()));
+
+    tracing::info!(
+        "🖼️ Found {} groups of similar images among {}",
+        groups.len(),
+        images.len()
+    );
+    Ok(groups)
+}
+
+/// Heap slot ordered by file size only
+struct BySize(FileEntry);
+
+impl PartialEq for BySize {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.size == other.0.size
+    }
+}
+
+impl Eq for BySize {}
+
+impl PartialOrd for BySize {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BySize {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.size.cmp(&other.0.size)
+    }
+}
+
+/// Heap slot ordered by modification time only
+struct ByModified(DateTime<Utc>, FileEntry);
+
+impl PartialEq for ByModified {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for ByModified {}
+
+impl PartialOrd for ByModified {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByModified {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Bounded min-heap holding the `limit` largest files seen so far.
+/// Memory stays O(limit) no matter how many files are offered. Directories
+/// can be ranked the same way once their sizes are aggregated.
+pub struct LargestFiles {
+    limit: usize,
+    heap: BinaryHeap<Reverse<BySize>>,
+}
+
+impl LargestFiles {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit),
+        }
+    }
+
+    /// Whether a file of this size would enter the ranking.
+    /// Lets callers skip building entries that would be dropped immediately.
+    pub fn accepts(&self, size: u64) -> bool {
+        if self.heap.len() < self.limit {
+            return true;
+        }
+        self.heap.peek().map(|Reverse(min)| size > min.0.size).unwrap_or(false)
+    }
+
+    /// Offer an entry, evicting the smallest one once the heap is full
+    pub fn push(&mut self, entry: FileEntry) {
+        if !self.accepts(entry.size) {
+            return;
+        }
+        if self.heap.len() == self.limit {
+            self.heap.pop();
+        }
+        self.heap.push(Reverse(BySize(entry)));
+    }
+
+    /// Ranked entries, largest first
+    pub fn into_sorted_vec(self) -> Vec<FileEntry> {
+        // Ascending order of Reverse is descending by size
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(BySize(entry))| entry)
+            .collect()
+    }
+}
+
+/// The `limit` directories with the largest recursive sizes, biggest first.
+/// Sizes must already be aggregated. The scanned roots are left out, as they
+/// would always rank first.
+pub fn largest_directories(entries: Vec<FileEntry>, limit: usize) -> Vec<FileEntry> {
+    let mut largest = LargestFiles::new(limit);
+    for entry in entries {
+        if entry.is_directory && entry.depth > 0 {
+            largest.push(entry);
+        }
+    }
+    largest.into_sorted_vec()
+}
+
+/// Bounded min-heap holding the `limit` most recently modified files seen so
+/// far, keyed by the modification time the scanner already parsed
+pub struct NewestFiles {
+    limit: usize,
+    heap: BinaryHeap<Reverse<ByModified>>,
+}
+
+impl NewestFiles {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit),
+        }
+    }
+
+    /// Whether a file modified at this instant would enter the ranking
+    pub fn accepts(&self, modified: DateTime<Utc>) -> bool {
+        if self.heap.len() < self.limit {
+            return true;
+        }
+        self.heap.peek().map(|Reverse(oldest)| modified > oldest.0).unwrap_or(false)
+    }
+
+    /// Offer an entry, evicting the oldest one once the heap is full
+    pub fn push(&mut self, modified: DateTime<Utc>, entry: FileEntry) {
+        if !self.accepts(modified) {
+            return;
+        }
+        if self.heap.len() == self.limit {
+            self.heap.pop();
+        }
+        self.heap.push(Reverse(ByModified(modified, entry)));
+    }
+
+    /// Ranked entries, newest first
+    pub fn into_sorted_vec(self) -> Vec<FileEntry> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ByModified(_, entry))| entry)
+            .collect()
+    }
+}
+
+/// Filter and sort entries by a query, ignoring its page window.
+/// Sorting is stable, so ties keep their scan order in both directions.
+/// Names compare case-insensitively; entries without a modified time sort first.
+pub fn select_entries<'a>(entries: &'a [FileEntry], params: &QueryParams) -> Vec<&'a FileEntry> {
+    let needle = params.name_contains.as_deref().map(str::to_lowercase);
+    let mut matched: Vec<&FileEntry> = entries
+        .iter()
+        .filter(|e| {
+            needle
+                .as_deref()
+                .map_or(true, |needle| e.name.to_lowercase().contains(needle))
+        })
+        .collect();
+
+    let compare = |a: &&FileEntry, b: &&FileEntry| match params.sort_by {
+        EntryField::Path => a.path.cmp(&b.path),
+        EntryField::Name => a
+            .name
+            .chars()
+            .flat_map(char::to_lowercase)
+            .cmp(b.name.chars().flat_map(char::to_lowercase)),
+        EntryField::Size => a.size.cmp(&b.size),
+        // Entries of one result share a format, so only one of these is set
+        EntryField::Modified => a
+            .modified_ms
+            .cmp(&b.modified_ms)
+            .then_with(|| a.modified.cmp(&b.modified)),
+    };
+
+    if params.descending {
+        matched.sort_by(|a, b| compare(b, a));
+    } else {
+        matched.sort_by(compare);
+    }
+    matched
+}
+
+/// Filter, sort and slice a stored scan's entries into one page
+pub fn query_entries(result: &ScanResult, params: &QueryParams) -> EntryPage {
+    let matched = select_entries(&result.entries, params);
+
+    EntryPage {
+        scan_id: result.scan_id.clone(),
+        total_matched: matched.len() as u64,
+        entries: matched
+            .into_iter()
+            .skip(params.offset)
+            .take(params.limit)
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Whether a file present in both scans changed size or modified time
+fn is_modified(old: &FileEntry, new: &FileEntry) -> bool {
+    !new.is_directory
+        && (old.size != new.size
+            || old.modified != new.modified
+            || old.modified_ms != new.modified_ms)
+}
+
+/// Compare two results by path in O(n).
+/// Only non-directories can be "modified": a directory's mtime moves whenever
+/// its children change, which would just repeat the added/removed lists.
+pub fn diff_results(base: &ScanResult, new: &ScanResult) -> ScanDiff {
+    let base_by_path: HashMap<&str, &FileEntry> =
+        base.entries.iter().map(|e| (e.path.as_str(), e)).collect();
+    let new_by_path: HashMap<&str, &FileEntry> =
+        new.entries.iter().map(|e| (e.path.as_str(), e)).collect();
+
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    for entry in &new.entries {
+        match base_by_path.get(entry.path.as_str()) {
+            None => added.push(entry.clone()),
+            Some(old) if is_modified(old, entry) => modified.push(entry.clone()),
+            Some(_) => {}
+        }
+    }
+
+    let removed = base
+        .entries
+        .iter()
+        .filter(|e| !new_by_path.contains_key(e.path.as_str()))
+        .map(|e| e.path.clone())
+        .collect();
+
+    ScanDiff {
+        added,
+        removed,
+        modified,
+        size_delta: new.total_size as i64 - base.total_size as i64,
+    }
+}
+
+/// Directories first, then case-insensitive name, so trees list stably
+pub fn tree_order(a: &&FileEntry, b: &&FileEntry) -> Ordering {
+    b.is_directory
+        .cmp(&a.is_directory)
+        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+/// Nest flat entries by path in O(n), one tree per top-level entry.
+/// Scan roots, and entries whose parent was not collected, are top-level.
+/// Nodes `max_depth` levels down keep only a count of their children.
+pub fn build_tree(entries: &[FileEntry], max_depth: Option<usize>) -> Vec<TreeNode> {
+    let paths: HashSet<&Path> = entries.iter().map(|e| Path::new(&e.path)).collect();
+
+    let mut roots: Vec<&FileEntry> = Vec::new();
+    let mut children: HashMap<&Path, Vec<&FileEntry>> = HashMap::new();
+    for entry in entries {
+        match Path::new(&entry.path).parent() {
+            Some(parent) if paths.contains(parent) => {
+                children.entry(parent).or_default().push(entry)
+            }
+            _ => roots.push(entry),
+        }
+    }
+    roots.sort_by(|a, b| a.path.cmp(&b.path));
+    for kids in children.values_mut() {
+        kids.sort_by(tree_order);
+    }
+
+    let max_depth = max_depth.unwrap_or(usize::MAX);
+    roots
+        .into_iter()
+        .map(|root| tree_node(root, &children, max_depth, 0))
+        .collect()
+}
+
+fn tree_node(
+    entry: &FileEntry,
+    children: &HashMap<&Path, Vec<&FileEntry>>,
+    max_depth: usize,
+    level: usize,
+) -> TreeNode {
+    let kids = children
+        .get(Path::new(&entry.path))
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    if level >= max_depth {
+        return TreeNode {
+            entry: entry.clone(),
+            children: Vec::new(),
+            omitted_children: kids.len() as u64,
+        };
+    }
+
+    TreeNode {
+        entry: entry.clone(),
+        children: kids
+            .iter()
+            .map(|kid| tree_node(kid, children, max_depth, level + 1))
+            .collect(),
+        omitted_children: 0,
+    }
+}
+
+/// Files, directories and file bytes among some entries
+#[derive(Default)]
+struct EntryTally {
+    files: u64,
+    directories: u64,
+    bytes: u64,
+}
+
+impl EntryTally {
+    fn add(&mut self, entry: &FileEntry) {
+        if entry.is_directory {
+            self.directories += 1;
+        } else if entry.is_file {
+            self.files += 1;
+            self.bytes += entry.size;
+        }
+    }
+}
+
+/// Shift a total by what a rescan removed and added
+fn adjust(total: u64, removed: u64, added: u64) -> u64 {
+    total.saturating_sub(removed) + added
+}
+
+/// Replace everything `base` holds at or below `subtree` with the matching
+/// entries and errors of `rescanned`, a walk of `root` restricted to `subtree`.
+///
+/// Fresh entries take the place of the old ones so the subtree stays in one
+/// run. Totals, the summary of `root` and, when `aggregated` is set, the sizes
+/// of the subtree's ancestor directories shift by the difference between the
+/// old and new entries, so files the scan counted without collecting are not
+/// re-counted.
+pub fn splice_subtree(
+    base: &ScanResult,
+    rescanned: ScanResult,
+    root: &str,
+    subtree: &Path,
+    aggregated: bool,
+    byte_unit: ByteUnit,
+) -> ScanResult {
+    let inside = |path: &str| Path::new(path).starts_with(subtree);
+
+    let mut removed = EntryTally::default();
+    let mut depths = DepthHistogram::from_buckets(&base.depth_distribution);
+    let mut insert_at = None;
+    let mut entries = Vec::with_capacity(base.entries.len());
+    for entry in &base.entries {
+        if inside(&entry.path) {
+            removed.add(entry);
+            if entry.is_directory || entry.is_file {
+                depths.remove(entry.depth, entry.is_directory);
+            }
+            insert_at.get_or_insert(entries.len());
+        } else {
+            entries.push(entry.clone());
+        }
+    }
+
+    // The walk also yields the directories leading down to the subtree
+    let mut added = EntryTally::default();
+    let fresh: Vec<FileEntry> = rescanned
+        .entries
+        .into_iter()
+        .filter(|entry| inside(&entry.path))
+        .inspect(|entry| {
+            added.add(entry);
+            if entry.is_directory || entry.is_file {
+                depths.add(entry.depth, entry.is_directory);
+            }
+        })
+        .collect();
+    let is_new = insert_at.is_none() && !fresh.is_empty();
+    let at = insert_at.unwrap_or(entries.len());
+    entries.splice(at..at, fresh);
+
+    if aggregated {
+        for dir in entries
+            .iter_mut()
+            .filter(|e| e.is_directory && subtree.starts_with(&e.path) && !inside(&e.path))
+        {
+            dir.size = adjust(dir.size, removed.bytes, added.bytes);
+            dir.size_formatted = format_bytes(dir.size, byte_unit);
+            if is_new && subtree.parent() == Some(Path::new(&dir.path)) {
+                dir.children_count = dir.children_count.map(|count| count + 1);
+            }
+        }
+    }
+
+    let mut roots = base.roots.clone();
+    if let Some(summary) = roots.iter_mut().find(|summary| summary.path == root) {
+        summary.total_files = adjust(summary.total_files, removed.files, added.files);
+        summary.total_directories =
+            adjust(summary.total_directories, removed.directories, added.directories);
+        summary.total_size = adjust(summary.total_size, removed.bytes, added.bytes);
+        summary.total_size_formatted = format_bytes(summary.total_size, byte_unit);
+    }
+
+    let mut errors: Vec<_> = base
+        .errors
+        .iter()
+        .filter(|error| !inside(&error.path))
+        .cloned()
+        .collect();
+    errors.extend(rescanned.errors.into_iter().filter(|error| inside(&error.path)));
+
+    let total_size = adjust(base.total_size, removed.bytes, added.bytes);
+    ScanResult {
+        scan_id: base.scan_id.clone(),
+        root_path: base.root_path.clone(),
+        roots,
+        total_files: adjust(base.total_files, removed.files, added.files),
+        total_directories: adjust(base.total_directories, removed.directories, added.directories),
+        total_size,
+        total_size_formatted: format_bytes(total_size, byte_unit),
+        entries,
+        errors,
+        extension_stats: base.extension_stats.clone(),
+        age_buckets: base.age_buckets.clone(),
+        category_stats: base.category_stats.clone(),
+        depth_distribution: depths.into_buckets(),
+        duration_ms: base.duration_ms,
+        completed_at: Utc::now().to_rfc3339(),
+        truncated: base.truncated,
+        symlink_duplicates_skipped: base.symlink_duplicates_skipped,
+        pruned_directories: base.pruned_directories,
+        hash_algorithm: base.hash_algorithm,
+        status: base.status.clone(),
+        request: base.request.clone(),
+    }
+}
+
+/// Running per-extension totals, aggregated while walking
+#[derive(Debug, Default)]
+pub struct ExtensionTotals {
+    totals: HashMap<Option<String>, (u64, u64)>,
+}
+
+impl ExtensionTotals {
+    /// Count one regular file; extensions are grouped case-insensitively
+    pub fn add(&mut self, path: &Path, size: u64) {
+        let extension = file_extension(path).map(|e| e.to_lowercase());
+        let (count, total) = self.totals.entry(extension).or_default();
+        *count += 1;
+        *total += size;
+    }
+
+    /// One row per extension, largest total size first.
+    /// No synthetic total row is added; callers sum the rows if they need one.
+    pub fn into_stats(self, byte_unit: ByteUnit) -> Vec<ExtensionStat> {
+        let mut stats: Vec<ExtensionStat> = self
+            .totals
+            .into_iter()
+            .map(|(extension, (file_count, total_size))| ExtensionStat {
+                extension,
+                file_count,
+                total_size,
+                total_size_formatted: format_bytes(total_size, byte_unit),
+            })
+            .collect();
+
+        stats.sort_by_key(|s| Reverse(s.total_size));
+        stats
+    }
+}
+
+/// Running per-category totals, aggregated while walking
+#[derive(Debug, Default)]
+pub struct CategoryTotals {
+    totals: HashMap<FileCategory, (u64, u64)>,
+}
+
+impl CategoryTotals {
+    /// Count one regular file under the category of its extension
+    pub fn add(&mut self, path: &Path, size: u64) {
+        let category =
+            file_extension(path).map_or(FileCategory::Other, |e| classify_extension(&e));
+        let (count, total) = self.totals.entry(category).or_default();
+        *count += 1;
+        *total += size;
+    }
+
+    /// One row per category that has files, largest total size first
+    pub fn into_summaries(self, byte_unit: ByteUnit) -> Vec<CategorySummary> {
+        let mut summaries: Vec<CategorySummary> = self
+            .totals
+            .into_iter()
+            .map(|(category, (file_count, total_size))| CategorySummary {
+                category,
+                file_count,
+                total_size,
+                total_size_formatted: format_bytes(total_size, byte_unit),
+            })
+            .collect();
+
+        summaries.sort_by_key(|s| Reverse(s.total_size));
+        summaries
+    }
+}
+
+/// Age buckets by upper bound in days, youngest first; anything past the
+/// last bound is "older"
+const AGE_BUCKETS: [(&str, i64); 4] = [("<1d", 1), ("<7d", 7), ("<30d", 30), ("<1y", 365)];
+
+/// Running file-age histogram by modified time, aggregated while walking
+#[derive(Debug)]
+pub struct AgeHistogram {
+    now: DateTime<Utc>,
+    /// (files, bytes) per `AGE_BUCKETS` entry, then "older", then "unknown"
+    totals: [(u64, u64); AGE_BUCKETS.len() + 2],
+}
+
+impl AgeHistogram {
+    /// Ages are measured against the moment the histogram is created
+    pub fn new() -> Self {
+        Self {
+            now: Utc::now(),
+            totals: Default::default(),
+        }
+    }
+
+    /// Count one regular file. Timestamps in the future land in the youngest bucket.
+    pub fn add(&mut self, modified: Option<DateTime<Utc>>, size: u64) {
+        let index = match modified {
+            Some(modified) => {
+                let age = self.now.signed_duration_since(modified);
+                AGE_BUCKETS
+                    .iter()
+                    .position(|(_, days)| age < Duration::days(*days))
+                    .unwrap_or(AGE_BUCKETS.len())
+            }
+            None => AGE_BUCKETS.len() + 1,
+        };
+
+        let (count, total) = &mut self.totals[index];
+        *count += 1;
+        *total += size;
+    }
+
+    /// Every bucket in age order, including empty ones, so charts keep a fixed axis
+    pub fn into_buckets(self, byte_unit: ByteUnit) -> Vec<AgeBucket> {
+        AGE_BUCKETS
+            .iter()
+            .map(|(label, _)| *label)
+            .chain(["older", "unknown"])
+            .zip(self.totals)
+            .map(|(label, (file_count, total_size))| AgeBucket {
+                label: label.to_string(),
+                file_count,
+                total_size,
+                total_size_formatted: format_bytes(total_size, byte_unit),
+            })
+            .collect()
+    }
+}
+
+/// Running (files, directories) counts per walk depth, kept while walking
+#[derive(Debug, Default)]
+pub struct DepthHistogram {
+    counts: Vec<(u64, u64)>,
+}
+
+impl DepthHistogram {
+    /// Continue from an earlier distribution, e.g. to combine several roots
+    pub fn from_buckets(buckets: &[DepthBucket]) -> Self {
+        let mut histogram = Self::default();
+        histogram.add_buckets(buckets);
+        histogram
+    }
+
+    /// Counts at `depth`, adding empty levels down to it as needed
+    fn level(&mut self, depth: usize) -> &mut (u64, u64) {
+        if self.counts.len() <= depth {
+            self.counts.resize(depth + 1, (0, 0));
+        }
+        &mut self.counts[depth]
+    }
+
+    /// Count one regular file or directory
+    pub fn add(&mut self, depth: usize, is_directory: bool) {
+        let (files, directories) = self.level(depth);
+        if is_directory {
+            *directories += 1;
+        } else {
+            *files += 1;
+        }
+    }
+
+    /// Take back an entry counted earlier
+    pub fn remove(&mut self, depth: usize, is_directory: bool) {
+        if let Some((files, directories)) = self.counts.get_mut(depth) {
+            let count = if is_directory { directories } else { files };
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    /// Add the counts of another distribution level by level
+    pub fn add_buckets(&mut self, buckets: &[DepthBucket]) {
+        for bucket in buckets {
+            let (files, directories) = self.level(bucket.depth);
+            *files += bucket.file_count;
+            *directories += bucket.dir_count;
+        }
+    }
+
+    /// Every level down to the deepest non-empty one
+    pub fn into_buckets(mut self) -> Vec<DepthBucket> {
+        while self.counts.last() == Some(&(0, 0)) {
+            self.counts.pop();
+        }
+        self.counts
+            .into_iter()
+            .enumerate()
+            .map(|(depth, (file_count, dir_count))| DepthBucket {
+                depth,
+                file_count,
+                dir_count,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,129 @@
+// VELOX CORE - Scan Checkpoints
+// Coarse resume points so interrupted scans can skip finished subtrees
+
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::{RootSummary, ScanRequest};
+
+/// Bumped whenever the checkpoint layout changes incompatibly
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Progress of a scan at top-level-subtree granularity.
+///
+/// Walks are depth-first, so once the walker reaches the next top-level entry
+/// of a root, everything under the previous one has been seen. Finished
+/// subtrees are recorded by path, which keeps resuming independent of the
+/// order entries come back in. Totals are kept; collected entries are not, so
+/// a resumed result only lists entries walked after the resume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanCheckpoint {
+    pub version: u32,
+    /// Original request, replayed on resume
+    pub request: ScanRequest,
+    /// Roots walked to the end, with their subtotals
+    pub completed_roots: Vec<RootSummary>,
+    /// Root that was being walked when the checkpoint was written
+    pub current_root: Option<String>,
+    /// Top-level entries of `current_root` whose subtrees were fully walked
+    pub completed_subtrees: Vec<String>,
+    /// Totals before `current_root` was started
+    pub current_root_base: (u64, u64, u64),
+    /// Totals over completed roots, the current root directory and its
+    /// completed subtrees
+    pub total_files: u64,
+    pub total_directories: u64,
+    pub total_size: u64,
+    pub updated_at: String,
+}
+
+impl ScanCheckpoint {
+    /// Empty checkpoint for a scan that hasn't walked anything yet
+    pub fn new(request: ScanRequest) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            request,
+            completed_roots: Vec::new(),
+            current_root: None,
+            completed_subtrees: Vec::new(),
+            current_root_base: (0, 0, 0),
+            total_files: 0,
+            total_directories: 0,
+            total_size: 0,
+            updated_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Read a checkpoint written by an earlier scan
+    pub fn load(path: &Path) -> VeloxResult<Self> {
+        let contents = fs::read_to_string(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => VeloxError::InvalidPath(path.display().to_string()),
+            _ => VeloxError::io_at(path, e),
+        })?;
+
+        let checkpoint: Self = serde_json::from_str(&contents).map_err(|e| {
+            VeloxError::InvalidArgument(format!("malformed checkpoint {}: {}", path.display(), e))
+        })?;
+
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(VeloxError::InvalidArgument(format!(
+                "checkpoint {} has version {}, expected {}",
+                path.display(),
+                checkpoint.version,
+                CHECKPOINT_VERSION
+            )));
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Write the checkpoint via a temporary file so a crash mid-write never
+    /// leaves a truncated checkpoint behind
+    pub fn save(&mut self, path: &Path) -> VeloxResult<()> {
+        self.updated_at = Utc::now().to_rfc3339();
+        let json =
+            serde_json::to_vec(self).map_err(|e| VeloxError::Serialization(e.to_string()))?;
+
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, json).map_err(|e| VeloxError::io_at(&temp, e))?;
+        fs::rename(&temp, path).map_err(|e| VeloxError::io_at(path, e))?;
+        Ok(())
+    }
+
+    /// Start walking `root`, forgetting subtrees recorded for a previous root
+    pub fn begin_root(&mut self, root: &str, totals: (u64, u64, u64)) {
+        self.current_root = Some(root.to_string());
+        self.completed_subtrees.clear();
+        self.current_root_base = totals;
+    }
+
+    /// Record a fully walked top-level subtree of the current root
+    pub fn complete_subtree(&mut self, path: String, totals: (u64, u64, u64)) {
+        self.completed_subtrees.push(path);
+        self.set_totals(totals);
+    }
+
+    /// Record a fully walked root
+    pub fn complete_root(&mut self, summary: RootSummary, totals: (u64, u64, u64)) {
+        self.completed_roots.push(summary);
+        self.current_root = None;
+        self.completed_subtrees.clear();
+        self.set_totals(totals);
+    }
+
+    fn set_totals(&mut self, (files, directories, size): (u64, u64, u64)) {
+        self.total_files = files;
+        self.total_directories = directories;
+        self.total_size = size;
+    }
+
+    /// Whether `root` was already walked to the end
+    pub fn is_root_complete(&self, root: &str) -> bool {
+        self.completed_roots.iter().any(|r| r.path == root)
+    }
+}
@@ -0,0 +1,95 @@
+// VELOX CORE - File Classification
+// Extension-based categories so the frontend can group and iconize entries
+
+use std::path::Path;
+
+use crate::types::FileCategory;
+
+/// Multi-part extensions reported whole rather than by their last part
+pub const COMPOUND_EXTENSIONS: &[&str] = &["tar.gz", "tar.bz2", "tar.xz"];
+
+/// A path's extension, keeping its case. Known compound extensions win over
+/// the last part, so `archive.tar.gz` gives `tar.gz`. Dotfiles such as
+/// `.bashrc` and names ending in a dot have no extension.
+pub fn file_extension(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy();
+    let lower = name.to_ascii_lowercase();
+    let compound = COMPOUND_EXTENSIONS.iter().find(|compound| {
+        // A stem must precede the dot, as with single extensions
+        lower
+            .strip_suffix(*compound)
+            .and_then(|stem| stem.strip_suffix('.'))
+            .is_some_and(|stem| !stem.is_empty())
+    });
+    if let Some(compound) = compound {
+        return Some(name[name.len() - compound.len()..].to_string());
+    }
+
+    path.extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .filter(|e| !e.is_empty())
+}
+
+/// Map a file extension (with or without a leading dot, any case) to a category.
+/// Pure lookup with no IO; unknown extensions are `Other`.
+pub fn classify_extension(extension: &str) -> FileCategory {
+    let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+
+    match extension.as_str() {
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "svg" | "ico" | "tif" | "tiff"
+        | "heic" | "heif" | "avif" | "raw" | "cr2" | "nef" | "arw" | "dng" | "psd" => {
+            FileCategory::Image
+        }
+        "mp4" | "mkv" | "mov" | "avi" | "wmv" | "flv" | "webm" | "m4v" | "mpg" | "mpeg" | "3gp"
+        | "m2ts" => FileCategory::Video,
+        "mp3" | "wav" | "flac" | "aac" | "ogg" | "oga" | "opus" | "m4a" | "wma" | "aiff"
+        | "aif" | "mid" | "midi" => FileCategory::Audio,
+        "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" | "ods" | "odp"
+        | "rtf" | "txt" | "md" | "csv" | "epub" | "pages" | "numbers" | "key" | "tex" => {
+            FileCategory::Document
+        }
+        "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "7z" | "rar" | "lz4" | "lzma"
+        | "iso" | "dmg" | "cab" | "jar" | "deb" | "rpm" | "tar.gz" | "tar.bz2" | "tar.xz" => {
+            FileCategory::Archive
+        }
+        "rs" | "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "py" | "rb" | "go" | "java" | "kt"
+        | "kts" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "swift" | "php" | "lua" | "sh"
+        | "bash" | "zsh" | "ps1" | "sql" | "html" | "htm" | "css" | "scss" | "json" | "toml"
+        | "yaml" | "yml" | "xml" => FileCategory::Code,
+        "exe" | "msi" | "bat" | "cmd" | "com" | "app" | "apk" | "appimage" | "bin" | "dll"
+        | "so" | "dylib" => FileCategory::Executable,
+        _ => FileCategory::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extension(name: &str) -> Option<String> {
+        file_extension(Path::new(name))
+    }
+
+    #[test]
+    fn compound_extensions_are_kept_whole() {
+        assert_eq!(extension("backup.tar.gz").as_deref(), Some("tar.gz"));
+        assert_eq!(extension("dir/logs.tar.xz").as_deref(), Some("tar.xz"));
+        assert_eq!(extension("notes.gz").as_deref(), Some("gz"));
+        // Without a stem the name is a dotfile, not a compound extension
+        assert_eq!(extension(".tar.gz").as_deref(), Some("gz"));
+    }
+
+    #[test]
+    fn uppercase_extensions_keep_their_case() {
+        assert_eq!(extension("BACKUP.TAR.GZ").as_deref(), Some("TAR.GZ"));
+        assert_eq!(extension("Photo.JPG").as_deref(), Some("JPG"));
+        assert_eq!(classify_extension("TAR.GZ"), FileCategory::Archive);
+    }
+
+    #[test]
+    fn dotfiles_and_trailing_dots_have_no_extension() {
+        assert_eq!(extension(".bashrc"), None);
+        assert_eq!(extension("file."), None);
+        assert_eq!(extension("Makefile"), None);
+    }
+}
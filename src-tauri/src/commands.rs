@@ -1,15 +1,105 @@
 // VELOX CORE - Tauri Command Registry
 // Every frontend action has a corresponding async command
 
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
 use chrono::Utc;
-use tauri::{api::dialog::FileDialogBuilder, State, Window};
+use tauri::{api::dialog::FileDialogBuilder, AppHandle, ClipboardManager, State, Window};
 
-use crate::error::VeloxError;
-use crate::scanner::{DirectoryScanner, ScanConfig};
-use crate::state::VeloxState;
+use crate::analysis;
+use crate::checkpoint::ScanCheckpoint;
+use crate::desktop;
+use crate::error::{VeloxError, VeloxResult};
+use crate::estimate;
+use crate::export::{self, NdjsonWriter};
+use crate::hasher;
+use crate::preview;
+use crate::profiles;
+use crate::scanner::{self, DirectoryScanner, ProgressEmitter, ScanConfig};
+use crate::schema;
+use crate::sink::{NdjsonFileSink, ProgressSink};
+use crate::state::{VeloxConfig, VeloxState};
+use crate::system;
 use crate::types::{
-    HeartbeatResponse, ScanRequest, ScanResult, ScanSession, ScanStatus, SystemInfo,
+    format_bytes, AgeBucket, ByteUnit, CategorySummary, DiskUsage, DuplicateReport, EntryFilterSpec,
+    EntryKindFilter, EntryPage, ExportStats, ExtensionStat, FileEntry, HashAlgorithm,
+    HeartbeatResponse, LogLine, MatchCount, MetadataLevel, NdjsonScanSummary, QueryParams, ScanDiff,
+    ScanEstimate, ScanPhase, ScanPhaseEvent, ScanProfile, ScanProgress, ScanRequest, ScanResult,
+    ScanSession, ScanSnapshot, ScanStatus, ScanSummary, SimilarGroup, SizeSummary, SystemInfo,
+    TextPreview, TrashResult, TreeNode, ValidationReport, VerifyReport, VolumeInfo,
 };
+use crate::validation;
+use crate::watcher;
+
+/// File name of the persisted configuration inside the app config directory
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// Saved scan profiles, stored beside the configuration
+const PROFILES_FILE_NAME: &str = "profiles.json";
+
+/// Entries buffered between writes when a scan streams into a file
+const FILE_STREAM_BATCH_SIZE: usize = 1000;
+
+/// Register a session for `label`, respecting the concurrency limit, run
+/// `work` with it and release the session however the work ends
+async fn run_registered_scan<T, F, Fut>(
+    state: &VeloxState,
+    label: String,
+    work: F,
+) -> VeloxResult<T>
+where
+    F: FnOnce(Arc<ScanSession>) -> Fut,
+    Fut: Future<Output = VeloxResult<T>>,
+{
+    let scan_id = state.register_scan(ScanSession::new(label))?;
+    tracing::debug!("Created scan session: {}", scan_id);
+
+    let session = state
+        .get_scan(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    let result = work(session).await;
+    state.remove_scan(&scan_id);
+    result
+}
+
+/// Keep a history summary of a finished or cancelled scan, and with `retain`
+/// the full result too so it can be queried later
+fn record_outcome(
+    state: &VeloxState,
+    session: &ScanSession,
+    result: &VeloxResult<ScanResult>,
+    byte_unit: ByteUnit,
+    retain: bool,
+) {
+    match result {
+        Ok(scan_result) => {
+            state.record_scan(ScanSummary::from(scan_result));
+            if retain {
+                state.store_result(scan_result.clone());
+            }
+        }
+        Err(VeloxError::ScanCancelled) => state.record_scan(session.cancelled_summary(byte_unit)),
+        Err(_) => {}
+    }
+}
+
+/// Run blocking file work on the blocking thread pool so it doesn't stall
+/// the async runtime that IPC and progress events share
+async fn run_blocking<T, F>(work: F) -> VeloxResult<T>
+where
+    F: FnOnce() -> VeloxResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| VeloxError::Unknown(format!("Blocking task failed: {}", e)))?
+}
 
 /// Scan a directory recursively with progress streaming
 #[tauri::command]
@@ -18,37 +108,926 @@ pub async fn scan_directory(
     state: State<'_, VeloxState>,
     request: ScanRequest,
 ) -> Result<ScanResult, VeloxError> {
-    tracing::info!("📂 Scan requested for: {}", request.path);
+    tracing::info!("📂 Scan requested for: {}", request.root_label());
 
-    // Create a new scan session
-    let session = ScanSession::new(request.path.clone());
-    let scan_id = state.register_scan(session.clone());
+    // Build scan configuration before registering so invalid requests don't leak a session
+    let config = ScanConfig::from_request(&request, &state.config.read())?;
 
-    tracing::debug!("Created scan session: {}", scan_id);
+    let state = state.inner();
+    run_registered_scan(state, request.root_label(), |session| async move {
+        // Execute the scan, keeping the request so the result can be partly rescanned
+        let byte_unit = config.byte_unit;
+        let scanner = DirectoryScanner::new(session.clone(), window, config);
+        let result = scanner.scan().await.map(|scan_result| ScanResult {
+            request: Some(Arc::new(request)),
+            ..scan_result
+        });
 
-    // Get the session from state
-    let session_arc = state
-        .get_scan(&scan_id)
-        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+        record_outcome(state, &session, &result, byte_unit, true);
+        result
+    })
+    .await
+}
+
+/// Scan straight into an NDJSON file, one entry per line, without holding
+/// entries in memory or sending them over IPC. Progress events work as in
+/// `scan_directory`. A cancelled scan still leaves a valid file with the
+/// entries walked so far.
+#[tauri::command]
+pub async fn scan_to_ndjson_file(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+    output_path: String,
+) -> Result<NdjsonScanSummary, VeloxError> {
+    tracing::info!("📝 NDJSON scan requested for: {} -> {}", request.root_label(), output_path);
+
+    // Entries only ever exist in one batch, so there is nothing to aggregate
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.stream_batch_size = Some(FILE_STREAM_BATCH_SIZE);
+    config.aggregate_dir_sizes = false;
+
+    let writer = NdjsonWriter::create(Path::new(&output_path), false)?;
+
+    let state = state.inner();
+    let label = request.root_label();
+    let (result, written) = run_registered_scan(state, label, |session| async move {
+        let sink = NdjsonFileSink::new(window, writer, session.clone());
+        let byte_unit = config.byte_unit;
+        let scanner = DirectoryScanner::new(session.clone(), sink.clone(), config);
+        let result = scanner.scan().await;
+        let written = sink.finish();
+
+        record_outcome(state, &session, &result, byte_unit, false);
+        Ok((result, written))
+    })
+    .await?;
+
+    // A write failure is what cancelled the scan, so report it first
+    let stats = written?;
+    let scan = result?;
+
+    Ok(NdjsonScanSummary {
+        scan_id: scan.scan_id,
+        output_path,
+        total_files: scan.total_files,
+        total_directories: scan.total_directories,
+        total_size: scan.total_size,
+        total_size_formatted: scan.total_size_formatted,
+        lines_written: stats.lines_written,
+        bytes_written: stats.bytes_written,
+        duration_ms: scan.duration_ms,
+        status: scan.status,
+    })
+}
+
+/// Continue an interrupted scan from a checkpoint written via `checkpointPath`.
+/// Finished roots and top-level subtrees are skipped and their totals restored;
+/// the result only lists entries walked after the resume.
+#[tauri::command]
+pub async fn resume_scan_from_checkpoint(
+    window: Window,
+    state: State<'_, VeloxState>,
+    checkpoint_path: String,
+) -> Result<ScanResult, VeloxError> {
+    tracing::info!("⏩ Resume requested from checkpoint: {}", checkpoint_path);
+
+    let checkpoint = ScanCheckpoint::load(Path::new(&checkpoint_path))?;
+    let request = checkpoint.request.clone();
+
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.checkpoint_path = Some(checkpoint_path);
+    config.checkpoint = Some(checkpoint);
+
+    let state = state.inner();
+    run_registered_scan(state, request.root_label(), |session| async move {
+        let byte_unit = config.byte_unit;
+        let scanner = DirectoryScanner::new(session.clone(), window, config);
+        let result = scanner.scan().await;
+
+        record_outcome(state, &session, &result, byte_unit, true);
+        result
+    })
+    .await
+}
+
+/// Check a scan request without starting it: paths, patterns, filters and a rough size
+#[tauri::command]
+pub async fn validate_scan_request(request: ScanRequest) -> Result<ValidationReport, VeloxError> {
+    let report = validation::validate_request(&request);
+
+    tracing::debug!(
+        "Validated request for {}: {} errors, {} warnings",
+        request.root_label(),
+        report.errors.len(),
+        report.warnings.len()
+    );
+    Ok(report)
+}
+
+/// Predict how long a scan would take from a short sample of the top two
+/// levels of each root. Runs as a session, so `cancel_scan` can stop it.
+#[tauri::command]
+pub async fn estimate_scan(
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+) -> Result<ScanEstimate, VeloxError> {
+    tracing::info!("⏱️ Scan estimate requested for: {}", request.root_label());
+
+    let config = ScanConfig::from_request(&request, &state.config.read())?;
+
+    let result = run_registered_scan(&state, request.root_label(), |session| {
+        run_blocking(move || estimate::estimate_scan(&config, &session))
+    })
+    .await;
+
+    if let Ok(estimate) = &result {
+        tracing::debug!(
+            "Estimated {} files in ~{}ms ({:?}) for {}",
+            estimate.estimated_files,
+            estimate.estimated_duration_ms,
+            estimate.confidence,
+            request.root_label()
+        );
+    }
+    result
+}
+
+/// Scan a directory and report groups of files with identical content
+#[tauri::command]
+pub async fn find_duplicates(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+) -> Result<DuplicateReport, VeloxError> {
+    tracing::info!("🧬 Duplicate search requested for: {}", request.root_label());
+
+    // Hashing is done selectively afterwards, and entries must be collected
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.hash_files = false;
+    config.stream_batch_size = None;
+    config.entry_filter = EntryKindFilter::All;
+    config.only_empty_files = false;
+    config.metadata = MetadataLevel::Full;
+    let (algorithm, io_retries, byte_unit) =
+        (config.hash_algorithm, config.io_retries, config.byte_unit);
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session.clone(), window.clone(), config);
+        let scan = scanner.scan().await?;
+
+        window.emit_phase(&ScanPhaseEvent {
+            scan_id: session.id.to_string(),
+            phase: ScanPhase::Hashing,
+            detail: None,
+        });
+        analysis::find_duplicates(&scan.entries, &session, algorithm, io_retries, byte_unit).await
+    })
+    .await
+}
+
+/// Scan a directory and group visually similar images by perceptual hash.
+/// Every image is decoded, which is far slower than `find_duplicates`, so a
+/// progress update is queued for each image; `threshold` is the largest
+/// Hamming distance between hashes that still counts as similar.
+#[tauri::command]
+pub async fn find_similar_images(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+    threshold: u32,
+) -> Result<Vec<SimilarGroup>, VeloxError> {
+    tracing::info!("🖼️ Similar image search requested for: {}", request.root_label());
+
+    // Images are picked from the collected files by category afterwards
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.hash_files = false;
+    config.stream_batch_size = None;
+    config.entry_filter = EntryKindFilter::FilesOnly;
+    config.only_empty_files = false;
+    let (byte_unit, interval_ms, capacity) =
+        (config.byte_unit, config.progress_interval_ms, config.progress_channel_capacity);
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session.clone(), window.clone(), config);
+        let scan = scanner.scan().await?;
+
+        let scan_id = session.id.to_string();
+        window.emit_phase(&ScanPhaseEvent {
+            scan_id: scan_id.clone(),
+            phase: ScanPhase::Hashing,
+            detail: None,
+        });
+
+        let progress = ProgressEmitter::spawn(window.clone(), &session, interval_ms, capacity);
+        let start_time = Instant::now();
+        let mut bytes_read = 0;
+        let on_image = |done: usize, total: usize, image: &FileEntry| {
+            bytes_read += image.size;
+            let elapsed = start_time.elapsed();
+            let per_sec = done as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+            progress.send(ScanProgress {
+                scan_id: scan_id.clone(),
+                seq: 0,
+                current_path: image.path.clone(),
+                root: None,
+                files_scanned: done as u64,
+                directories_scanned: 0,
+                bytes_scanned: bytes_read,
+                bytes_scanned_formatted: format_bytes(bytes_read, byte_unit),
+                progress_percent: done as f64 / total as f64 * 100.0,
+                estimated_total: Some(total as u64),
+                files_per_sec: per_sec,
+                eta_ms: Some(((total - done) as f64 / per_sec * 1000.0) as u64),
+                elapsed_ms: elapsed.as_millis() as u64,
+                status: ScanStatus::Scanning,
+            });
+        };
+        let groups =
+            analysis::find_similar_images(&scan.entries, &session, threshold, on_image).await;
+        progress.finish().await;
+        groups
+    })
+    .await
+}
+
+/// Scan a directory and return its largest regular files, biggest first
+#[tauri::command]
+pub async fn find_largest_files(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+    limit: usize,
+) -> Result<Vec<FileEntry>, VeloxError> {
+    tracing::info!("📊 Largest {} files requested for: {}", limit, request.root_label());
+
+    if limit == 0 {
+        return Err(VeloxError::InvalidArgument("limit must be greater than 0".to_string()));
+    }
+
+    // Only the ranking is kept, so nothing is streamed, hashed, or aggregated
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.largest_files = Some(limit);
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.aggregate_dir_sizes = false;
+    config.metadata = MetadataLevel::Full;
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner.scan().await.map(|scan| scan.entries)
+    })
+    .await
+}
+
+/// Scan a directory and return the directories below it with the largest
+/// recursive sizes, biggest first
+#[tauri::command]
+pub async fn find_largest_directories(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+    limit: usize,
+) -> Result<Vec<FileEntry>, VeloxError> {
+    tracing::info!("📊 Largest {} directories requested for: {}", limit, request.root_label());
 
-    // Build scan configuration
+    if limit == 0 {
+        return Err(VeloxError::InvalidArgument("limit must be greater than 0".to_string()));
+    }
+
+    // Every file is needed for the recursive sizes; only directories are kept
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.aggregate_dir_sizes = true;
+    config.entry_filter = EntryKindFilter::DirectoriesOnly;
+    config.only_empty_files = false;
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.metadata = MetadataLevel::Full;
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner
+            .scan()
+            .await
+            .map(|scan| analysis::largest_directories(scan.entries, limit))
+    })
+    .await
+}
+
+/// Most recently modified regular files under `path` from the last
+/// `within_hours`, newest first. Memory stays bounded by `limit`.
+#[tauri::command]
+pub async fn find_recently_modified(
+    window: Window,
+    state: State<'_, VeloxState>,
+    path: String,
+    within_hours: u64,
+    limit: usize,
+) -> Result<Vec<FileEntry>, VeloxError> {
+    tracing::info!("🕒 Files modified in the last {}h requested for: {}", within_hours, path);
+
+    if limit == 0 {
+        return Err(VeloxError::InvalidArgument("limit must be greater than 0".to_string()));
+    }
+    if within_hours == 0 {
+        return Err(VeloxError::InvalidArgument("withinHours must be greater than 0".to_string()));
+    }
+
+    // A window reaching past what the clock can represent covers everything
+    let modified_after = i64::try_from(within_hours)
+        .ok()
+        .and_then(chrono::Duration::try_hours)
+        .and_then(|window| Utc::now().checked_sub_signed(window));
+
+    // Only the ranking is kept, so nothing is streamed, hashed, or aggregated
     let config = ScanConfig {
-        max_depth: request.max_depth.unwrap_or(100),
-        include_hidden: request.include_hidden,
-        follow_symlinks: request.follow_symlinks,
-        progress_interval_ms: 50,
+        modified_after,
+        newest_files: Some(limit),
+        metadata: MetadataLevel::Full,
+        ..ScanConfig::from_app_config(&state.config.read())
     };
 
-    // Execute the scan
-    let scanner = DirectoryScanner::new(session_arc, window, config);
-    let result = scanner.scan().await;
+    run_registered_scan(&state, path, |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner.scan().await.map(|scan| scan.entries)
+    })
+    .await
+}
 
-    // Clean up the session
-    state.remove_scan(&scan_id);
+/// Scan a directory and break down disk usage by file extension.
+/// Rows are sorted by total size, largest first. No total row is included;
+/// sum the rows for overall figures.
+#[tauri::command]
+pub async fn analyze_by_extension(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+) -> Result<Vec<ExtensionStat>, VeloxError> {
+    tracing::info!("📊 Extension breakdown requested for: {}", request.root_label());
+
+    // Only running totals are kept, so nothing is streamed, hashed, or aggregated
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.group_by_extension = true;
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.aggregate_dir_sizes = false;
+    config.metadata = MetadataLevel::Full;
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner
+            .scan()
+            .await
+            .map(|scan| scan.extension_stats.unwrap_or_default())
+    })
+    .await
+}
+
+/// Scan a directory and break down disk usage by file category.
+/// Only regular files are counted, so directories never get a row. Rows are
+/// sorted by total size, largest first, and empty categories are omitted.
+#[tauri::command]
+pub async fn analyze_by_category(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+) -> Result<Vec<CategorySummary>, VeloxError> {
+    tracing::info!("📊 Category breakdown requested for: {}", request.root_label());
+
+    // Only running totals are kept, so nothing is streamed, hashed, or aggregated
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.group_by_category = true;
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.aggregate_dir_sizes = false;
+    config.metadata = MetadataLevel::Full;
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner
+            .scan()
+            .await
+            .map(|scan| scan.category_stats.unwrap_or_default())
+    })
+    .await
+}
+
+/// Scan a directory and bucket its files by last-modified age.
+/// Buckets always come back youngest first with `unknown` last, empty ones included.
+#[tauri::command]
+pub async fn analyze_file_ages(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+) -> Result<Vec<AgeBucket>, VeloxError> {
+    tracing::info!("📊 File age breakdown requested for: {}", request.root_label());
+
+    // Only running totals are kept, so nothing is streamed, hashed, or aggregated
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.group_by_age = true;
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.aggregate_dir_sizes = false;
+    config.metadata = MetadataLevel::Full;
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner
+            .scan()
+            .await
+            .map(|scan| scan.age_buckets.unwrap_or_default())
+    })
+    .await
+}
+
+/// Total up a directory without collecting entries, for a quick "folder size".
+/// Filters, cancellation and progress events work as in `scan_directory`.
+#[tauri::command]
+pub async fn compute_directory_size(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+) -> Result<SizeSummary, VeloxError> {
+    tracing::info!("📏 Folder size requested for: {}", request.root_label());
+
+    // Only the session totals are kept, so nothing is streamed, hashed, or aggregated
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.totals_only = true;
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.aggregate_dir_sizes = false;
+    config.metadata = MetadataLevel::Full;
+
+    run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner.scan().await.map(|scan| SizeSummary::from(&scan))
+    })
+    .await
+}
 
+/// Count the files, directories and bytes matching a filter spec, e.g. "PDFs
+/// over 10 MB", without collecting entries. Filters, cancellation and
+/// progress events work as in `scan_directory`.
+#[tauri::command]
+pub async fn count_matching(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+    filter: EntryFilterSpec,
+) -> Result<MatchCount, VeloxError> {
+    tracing::info!("🔢 Match count requested for: {}", request.root_label());
+
+    let mut request = request.with_filter(filter);
+    // Filtered-out files must not count, and bytes need sizes to be read
+    request.count_filtered = Some(false);
+    if request.metadata == Some(MetadataLevel::None) {
+        request.metadata = Some(MetadataLevel::Basic);
+    }
+
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.totals_only = true;
+    config.stream_batch_size = None;
+    config.hash_files = false;
+    config.aggregate_dir_sizes = false;
+
+    let result = run_registered_scan(&state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session, window, config);
+        scanner.scan().await.map(|scan| MatchCount::from(&scan))
+    })
+    .await;
+
+    if let Ok(count) = &result {
+        tracing::info!(
+            "🔢 {} files ({}) matched in {}",
+            count.files,
+            count.total_size_formatted,
+            request.root_label()
+        );
+    }
     result
 }
 
+/// Recompute SHA-256 digests for every file in a `path,sha256` manifest
+#[tauri::command]
+pub async fn verify_checksums(manifest_path: String) -> Result<VerifyReport, VeloxError> {
+    tracing::info!("🔐 Checksum verification requested: {}", manifest_path);
+
+    let manifest = PathBuf::from(&manifest_path);
+    let report = run_blocking(move || hasher::verify_manifest(&manifest)).await?;
+
+    tracing::info!(
+        "🔐 Verified {} files: {} matched, {} mismatched, {} missing, {} unreadable",
+        report.entries.len(),
+        report.matched,
+        report.mismatched,
+        report.missing,
+        report.unreadable
+    );
+    Ok(report)
+}
+
+/// Hash a single file on demand, e.g. to check a download
+#[tauri::command]
+pub async fn hash_file(path: String, algorithm: HashAlgorithm) -> Result<String, VeloxError> {
+    tracing::info!("🔐 {:?} hash requested: {}", algorithm, path);
+    run_blocking(move || hasher::hash_file(Path::new(&path), algorithm)).await
+}
+
+/// Get summaries of recently finished scans, newest first
+#[tauri::command]
+pub async fn get_scan_history(state: State<'_, VeloxState>) -> Result<Vec<ScanSummary>, VeloxError> {
+    Ok(state.scan_history())
+}
+
+/// Fetch a retained scan result again, e.g. after the UI reloads
+#[tauri::command]
+pub async fn get_scan_result(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+) -> Result<ScanResult, VeloxError> {
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    Ok(ScanResult::clone(&result))
+}
+
+/// Filter, sort and page through a retained scan's entries for virtual scrolling
+#[tauri::command]
+pub async fn query_scan_entries(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    params: QueryParams,
+) -> Result<EntryPage, VeloxError> {
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    Ok(analysis::query_entries(&result, &params))
+}
+
+/// Scan a directory and keep only the entries matching `params`, sorted, as a
+/// retained result. Returns the first page, so large scans never cross IPC
+/// in full; later pages come from `query_scan_entries` with the page's
+/// `scan_id`. Totals still describe everything the walk found.
+#[tauri::command]
+pub async fn scan_with_query(
+    window: Window,
+    state: State<'_, VeloxState>,
+    request: ScanRequest,
+    params: QueryParams,
+) -> Result<EntryPage, VeloxError> {
+    tracing::info!("🔎 Query scan requested for: {}", request.root_label());
+
+    let mut config = ScanConfig::from_request(&request, &state.config.read())?;
+    config.stream_batch_size = None;
+    let byte_unit = config.byte_unit;
+
+    let selection = &params;
+    let state = state.inner();
+    let result = run_registered_scan(state, request.root_label(), |session| async move {
+        let scanner = DirectoryScanner::new(session.clone(), window, config);
+        let result = scanner.scan().await.map(|scan_result| {
+            let entries = analysis::select_entries(&scan_result.entries, selection)
+                .into_iter()
+                .cloned()
+                .collect();
+            ScanResult {
+                entries,
+                request: Some(Arc::new(request)),
+                ..scan_result
+            }
+        });
+
+        // Retained like any finished scan so the remaining pages can be queried
+        record_outcome(state, &session, &result, byte_unit, true);
+        result
+    })
+    .await;
+
+    // Filtering and sorting the retained entries again changes nothing, so
+    // this only takes the page window
+    result.map(|scan_result| analysis::query_entries(&scan_result, &params))
+}
+
+/// Nest a retained scan's entries into trees for a collapsible view, one per
+/// scanned root. `max_depth` caps how many levels below each root are nested.
+#[tauri::command]
+pub async fn get_scan_tree(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    max_depth: Option<usize>,
+) -> Result<Vec<TreeNode>, VeloxError> {
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    Ok(analysis::build_tree(&result.entries, max_depth))
+}
+
+/// Compare two retained results of the same tree, e.g. yesterday's and today's
+#[tauri::command]
+pub async fn diff_scans(
+    state: State<'_, VeloxState>,
+    base_scan_id: String,
+    new_scan_id: String,
+) -> Result<ScanDiff, VeloxError> {
+    let base = state
+        .get_result(&base_scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(base_scan_id.clone()))?;
+    let new = state
+        .get_result(&new_scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(new_scan_id.clone()))?;
+
+    let diff = analysis::diff_results(&base, &new);
+
+    tracing::info!(
+        "🔀 Diffed {} against {}: {} added, {} removed, {} modified",
+        new_scan_id,
+        base_scan_id,
+        diff.added.len(),
+        diff.removed.len(),
+        diff.modified.len()
+    );
+    Ok(diff)
+}
+
+/// Walk one folder of a retained scan again, e.g. after the user changed it,
+/// and splice the fresh entries into that result with the original request's
+/// settings. The merged result is retained under the same scan ID.
+#[tauri::command]
+pub async fn rescan_subtree(
+    window: Window,
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    subtree_path: String,
+) -> Result<ScanResult, VeloxError> {
+    tracing::info!("🔁 Subtree rescan requested for {}: {}", scan_id, subtree_path);
+
+    let base = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+    let request = base.request.as_ref().ok_or_else(|| {
+        VeloxError::InvalidArgument(format!("Scan {} can't be rescanned in part", scan_id))
+    })?;
+    if request.stream_batch_size.is_some_and(|size| size > 0) {
+        return Err(VeloxError::InvalidArgument(format!(
+            "Scan {} streamed its entries, so there are none to update",
+            scan_id
+        )));
+    }
+
+    scanner::validate_root(&subtree_path)?;
+    let (root, subtree) =
+        scanner::locate_subtree(&request.root_paths(), &subtree_path).ok_or_else(|| {
+            VeloxError::InvalidPath(format!("{} is not inside the scanned roots", subtree_path))
+        })?;
+
+    // Walk from the original root so depth limits and globs apply as before
+    let mut config = ScanConfig::from_request(request, &state.config.read())?;
+    config.roots = vec![root.clone()];
+    config.subtree = Some(subtree.clone());
+    config.max_entries = None;
+    config.checkpoint_path = None;
+    config.checkpoint = None;
+    let (aggregated, byte_unit) = (config.aggregate_dir_sizes, config.byte_unit);
+
+    let rescanned = run_registered_scan(&state, subtree_path.clone(), |session| async move {
+        DirectoryScanner::new(session, window, config).scan().await
+    })
+    .await?;
+
+    let merged = analysis::splice_subtree(&base, rescanned, &root, &subtree, aggregated, byte_unit);
+    tracing::info!(
+        "🔁 Rescanned {} in {}: {} entries now retained",
+        subtree.display(),
+        scan_id,
+        merged.entries.len()
+    );
+
+    state.store_result(merged.clone());
+    Ok(merged)
+}
+
+/// Drop all retained scan results to free memory, returning how many were cleared
+#[tauri::command]
+pub async fn clear_scan_results(state: State<'_, VeloxState>) -> Result<usize, VeloxError> {
+    let cleared = state.clear_results();
+    tracing::info!("🧹 Cleared {} stored scan results", cleared);
+    Ok(cleared)
+}
+
+/// Export a completed scan to CSV, one row per entry. The file is gzipped when
+/// `compress` is set, or when it's omitted and the path ends in `.gz`.
+#[tauri::command]
+pub async fn export_scan_csv(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    output_path: String,
+    compress: Option<bool>,
+) -> Result<ExportStats, VeloxError> {
+    tracing::info!("📤 CSV export requested for scan: {}", scan_id);
+
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    let output = PathBuf::from(output_path);
+    let compress = export::wants_gzip(&output, compress);
+    run_blocking(move || export::write_csv(&result, &output, compress)).await
+}
+
+/// Export a completed scan as newline-delimited JSON, one entry per line.
+/// Compression follows the same rules as `export_scan_csv`.
+#[tauri::command]
+pub async fn export_scan_ndjson(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    output_path: String,
+    compress: Option<bool>,
+) -> Result<ExportStats, VeloxError> {
+    tracing::info!("📤 NDJSON export requested for scan: {}", scan_id);
+
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    let output = PathBuf::from(output_path);
+    let compress = export::wants_gzip(&output, compress);
+    run_blocking(move || export::write_ndjson(&result, &output, compress)).await
+}
+
+/// Export a completed scan as a self-contained HTML report, returning its path
+#[tauri::command]
+pub async fn export_scan_html(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    output_path: String,
+) -> Result<String, VeloxError> {
+    tracing::info!("📤 HTML export requested for scan: {}", scan_id);
+
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    let output = PathBuf::from(&output_path);
+    run_blocking(move || export::write_html(&result, &output)).await?;
+    Ok(output_path)
+}
+
+/// Export a completed scan as an indented Markdown tree, capped at `max_depth` levels
+#[tauri::command]
+pub async fn export_scan_tree_markdown(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    output_path: String,
+    max_depth: Option<usize>,
+) -> Result<ExportStats, VeloxError> {
+    tracing::info!("📤 Markdown tree export requested for scan: {}", scan_id);
+
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    let output = PathBuf::from(output_path);
+    run_blocking(move || export::write_markdown_tree(&result, &output, max_depth)).await
+}
+
+/// Export a completed scan into a SQLite database for ad-hoc SQL queries,
+/// returning the number of rows written
+#[tauri::command]
+pub async fn export_scan_sqlite(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+    db_path: String,
+) -> Result<u64, VeloxError> {
+    tracing::info!("📤 SQLite export requested for scan: {}", scan_id);
+
+    let result = state
+        .get_result(&scan_id)
+        .ok_or_else(|| VeloxError::NoActiveScan(scan_id.clone()))?;
+
+    let output = PathBuf::from(db_path);
+    run_blocking(move || export::write_sqlite(&result, &output)).await
+}
+
+/// List in-flight scans with their live counters
+#[tauri::command]
+pub async fn list_active_scans(state: State<'_, VeloxState>) -> Result<Vec<ScanSnapshot>, VeloxError> {
+    Ok(state.active_scan_snapshots())
+}
+
+/// Get metadata for a single path without running a scan
+#[tauri::command]
+pub async fn get_file_info(
+    state: State<'_, VeloxState>,
+    path: String,
+) -> Result<FileEntry, VeloxError> {
+    tracing::debug!("🔎 File info requested: {}", path);
+
+    let byte_unit = state.config.read().byte_unit;
+    FileEntry::from_path(Path::new(&path), byte_unit).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => VeloxError::InvalidPath(path.clone()),
+        io::ErrorKind::PermissionDenied => VeloxError::AccessDenied(path.clone()),
+        _ => VeloxError::io_at(&path, e),
+    })
+}
+
+/// Read the start of a text file for an inline preview
+#[tauri::command]
+pub async fn read_text_preview(
+    path: String,
+    max_bytes: usize,
+) -> Result<TextPreview, VeloxError> {
+    tracing::debug!("👁️ Preview requested: {}", path);
+    preview::read_text_preview(Path::new(&path), max_bytes)
+}
+
+/// Show a file or folder selected in the system file manager
+#[tauri::command]
+pub async fn reveal_in_file_manager(path: String) -> Result<(), VeloxError> {
+    tracing::info!("📁 Reveal requested for: {}", path);
+    desktop::reveal_in_file_manager(Path::new(&path))
+}
+
+/// Open a file or folder with the system default application
+#[tauri::command]
+pub async fn open_path(path: String) -> Result<(), VeloxError> {
+    tracing::info!("📂 Open requested for: {}", path);
+    desktop::open_path(Path::new(&path))
+}
+
+/// Move files or folders to the system trash, reporting each path's outcome
+#[tauri::command]
+pub async fn move_to_trash(paths: Vec<String>) -> Result<TrashResult, VeloxError> {
+    if paths.is_empty() {
+        return Err(VeloxError::InvalidArgument("no paths given".to_string()));
+    }
+
+    tracing::info!("🗑️ Trash requested for {} paths", paths.len());
+
+    let result = run_blocking(move || Ok(desktop::move_to_trash(&paths))).await?;
+
+    tracing::info!(
+        "🗑️ Trashed {} paths, {} failed",
+        result.trashed.len(),
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+/// Move or rename a file or folder, replacing an existing destination only
+/// when `overwrite` is set
+#[tauri::command]
+pub async fn move_path(from: String, to: String, overwrite: bool) -> Result<(), VeloxError> {
+    tracing::info!("📦 Move requested: {} -> {}", from, to);
+    // A cross-volume move copies the whole tree, which can take minutes
+    run_blocking(move || desktop::move_path(Path::new(&from), Path::new(&to), overwrite)).await
+}
+
+/// Put text such as a scanned path on the system clipboard
+#[tauri::command]
+pub async fn copy_to_clipboard(app: AppHandle, text: String) -> Result<(), VeloxError> {
+    app.clipboard_manager()
+        .write_text(text)
+        .map_err(|e| VeloxError::Unknown(format!("Clipboard error: {}", e)))
+}
+
+/// Watch a directory for changes, emitting `velox:fs:event` until stopped
+#[tauri::command]
+pub async fn watch_directory(
+    window: Window,
+    state: State<'_, VeloxState>,
+    path: String,
+) -> Result<String, VeloxError> {
+    tracing::info!("👁️ Watch requested for: {}", path);
+
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(VeloxError::InvalidPath(path));
+    }
+
+    let watch_id = uuid::Uuid::new_v4().to_string();
+    let watch = watcher::start_watch(watch_id.clone(), root, move |event| {
+        window.emit("velox:fs:event", &event).ok();
+    })?;
+
+    state.add_watch(watch_id.clone(), watch);
+    Ok(watch_id)
+}
+
+/// Stop a running directory watch
+#[tauri::command]
+pub async fn stop_watch(state: State<'_, VeloxState>, watch_id: String) -> Result<bool, VeloxError> {
+    tracing::info!("👁️ Stop requested for watch: {}", watch_id);
+
+    if state.remove_watch(&watch_id) {
+        Ok(true)
+    } else {
+        Err(VeloxError::NoActiveWatch(watch_id))
+    }
+}
+
 /// Cancel an active scan
 #[tauri::command]
 pub async fn cancel_scan(
@@ -64,6 +1043,44 @@ pub async fn cancel_scan(
     }
 }
 
+/// Cancel every active scan at once
+#[tauri::command]
+pub async fn cancel_all_scans(state: State<'_, VeloxState>) -> Result<usize, VeloxError> {
+    let cancelled = state.cancel_all();
+    tracing::info!("🛑 Cancelled {} active scans", cancelled);
+    Ok(cancelled)
+}
+
+/// Pause an active scan
+#[tauri::command]
+pub async fn pause_scan(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+) -> Result<bool, VeloxError> {
+    tracing::info!("⏸️ Pause requested for scan: {}", scan_id);
+
+    if state.pause_scan(&scan_id) {
+        Ok(true)
+    } else {
+        Err(VeloxError::NoActiveScan(scan_id))
+    }
+}
+
+/// Resume a paused scan
+#[tauri::command]
+pub async fn resume_scan(
+    state: State<'_, VeloxState>,
+    scan_id: String,
+) -> Result<bool, VeloxError> {
+    tracing::info!("▶️ Resume requested for scan: {}", scan_id);
+
+    if state.resume_scan(&scan_id) {
+        Ok(true)
+    } else {
+        Err(VeloxError::NoActiveScan(scan_id))
+    }
+}
+
 /// Get the status of an active scan
 #[tauri::command]
 pub async fn get_scan_status(
@@ -71,7 +1088,7 @@ pub async fn get_scan_status(
     scan_id: String,
 ) -> Result<ScanStatus, VeloxError> {
     if let Some(session) = state.get_scan(&scan_id) {
-        Ok(session.status.clone())
+        Ok(session.current_status())
     } else {
         Err(VeloxError::NoActiveScan(scan_id))
     }
@@ -79,7 +1096,9 @@ pub async fn get_scan_status(
 
 /// Get system information
 #[tauri::command]
-pub async fn get_system_info() -> Result<SystemInfo, VeloxError> {
+pub async fn get_system_info(state: State<'_, VeloxState>) -> Result<SystemInfo, VeloxError> {
+    let resources = system::sample_resources(&mut state.system.lock());
+
     Ok(SystemInfo {
         os: std::env::consts::OS.to_string(),
         arch: std::env::consts::ARCH.to_string(),
@@ -88,10 +1107,25 @@ pub async fn get_system_info() -> Result<SystemInfo, VeloxError> {
             .map(|h| h.to_string_lossy().to_string())
             .unwrap_or_else(|_| "unknown".to_string()),
         cpu_cores: num_cpus::get(),
+        total_memory_bytes: resources.total_memory_bytes,
+        available_memory_bytes: resources.available_memory_bytes,
+        cpu_usage_percent: resources.cpu_usage_percent,
         timestamp: Utc::now().to_rfc3339(),
     })
 }
 
+/// List mounted volumes with their free space
+#[tauri::command]
+pub async fn list_volumes() -> Result<Vec<VolumeInfo>, VeloxError> {
+    Ok(system::list_volumes())
+}
+
+/// Report capacity and free space for the volume containing a path
+#[tauri::command]
+pub async fn get_path_disk_usage(path: String) -> Result<DiskUsage, VeloxError> {
+    system::disk_usage_for(Path::new(&path))
+}
+
 /// Heartbeat for frontend-backend sync verification
 #[tauri::command]
 pub async fn heartbeat(state: State<'_, VeloxState>) -> Result<HeartbeatResponse, VeloxError> {
@@ -101,9 +1135,156 @@ pub async fn heartbeat(state: State<'_, VeloxState>) -> Result<HeartbeatResponse
         active_scans: state.active_scan_count(),
         timestamp: Utc::now().to_rfc3339(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        total_scans_started: state.scans_started.load(Ordering::Relaxed),
+        total_scans_completed: state.scans_completed.load(Ordering::Relaxed),
+        total_scans_cancelled: state.scans_cancelled.load(Ordering::Relaxed),
+        peak_active_scans: state.peak_active_scans.load(Ordering::Relaxed),
     })
 }
 
+/// The last `limit` captured log lines, oldest first, for the diagnostics panel
+#[tauri::command]
+pub async fn get_recent_logs(
+    state: State<'_, VeloxState>,
+    limit: usize,
+) -> Result<Vec<LogLine>, VeloxError> {
+    Ok(state.logs.recent(limit))
+}
+
+/// JSON Schemas for the public IPC types, keyed by type name
+#[tauri::command]
+pub async fn get_type_schemas() -> Result<serde_json::Value, VeloxError> {
+    schema::type_schemas()
+}
+
+/// Get the current application configuration
+#[tauri::command]
+pub async fn get_config(state: State<'_, VeloxState>) -> Result<VeloxConfig, VeloxError> {
+    Ok(state.config.read().clone())
+}
+
+/// Replace the application configuration and persist it to disk
+#[tauri::command]
+pub async fn update_config(
+    app: AppHandle,
+    state: State<'_, VeloxState>,
+    config: VeloxConfig,
+) -> Result<VeloxConfig, VeloxError> {
+    tracing::info!("⚙️ Updating configuration");
+
+    let path = config_file_path(&app)
+        .ok_or_else(|| VeloxError::Unknown("Unable to resolve config directory".to_string()))?;
+    config.save_to_disk(&path)?;
+
+    *state.config.write() = config.clone();
+
+    Ok(config)
+}
+
+/// Location of the persisted configuration file
+pub fn config_file_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path_resolver()
+        .app_config_dir()
+        .map(|dir| dir.join(CONFIG_FILE_NAME))
+}
+
+/// Location of the persisted scan profiles
+pub fn profiles_file_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path_resolver()
+        .app_config_dir()
+        .map(|dir| dir.join(PROFILES_FILE_NAME))
+}
+
+/// Write the profile list on the blocking pool, handing it back once saved
+async fn write_profiles(path: PathBuf, updated: Vec<ScanProfile>) -> VeloxResult<Vec<ScanProfile>> {
+    run_blocking(move || profiles::save_profiles(&path, &updated).map(|_| updated)).await
+}
+
+/// Save a scan request under a new, unique name so it can be rerun later
+#[tauri::command]
+pub async fn save_profile(
+    app: AppHandle,
+    state: State<'_, VeloxState>,
+    name: String,
+    request: ScanRequest,
+) -> Result<ScanProfile, VeloxError> {
+    tracing::info!("💾 Saving scan profile: {}", name);
+
+    // Refuse requests that could never run
+    ScanConfig::from_request(&request, &state.config.read())?;
+
+    let path = profiles_file_path(&app)
+        .ok_or_else(|| VeloxError::Unknown("Unable to resolve config directory".to_string()))?;
+
+    // One writer at a time; readers keep using the list while the file is written
+    let _writer = state.profile_writes.lock().await;
+    let mut updated = state.profiles.read().clone();
+    let profile = ScanProfile {
+        name: profiles::check_new_name(&name, &updated)?,
+        request,
+        saved_at: Utc::now().to_rfc3339(),
+    };
+
+    updated.push(profile.clone());
+    updated.sort_by_key(|profile| profile.name.to_lowercase());
+    let updated = write_profiles(path, updated).await?;
+    *state.profiles.write() = updated;
+
+    Ok(profile)
+}
+
+/// Saved scan profiles, sorted by name
+#[tauri::command]
+pub async fn list_profiles(state: State<'_, VeloxState>) -> Result<Vec<ScanProfile>, VeloxError> {
+    Ok(state.profiles.read().clone())
+}
+
+/// Delete a saved scan profile by name, ignoring case
+#[tauri::command]
+pub async fn delete_profile(
+    app: AppHandle,
+    state: State<'_, VeloxState>,
+    name: String,
+) -> Result<bool, VeloxError> {
+    tracing::info!("🗑️ Deleting scan profile: {}", name);
+
+    let path = profiles_file_path(&app)
+        .ok_or_else(|| VeloxError::Unknown("Unable to resolve config directory".to_string()))?;
+
+    // One writer at a time; readers keep using the list while the file is written
+    let _writer = state.profile_writes.lock().await;
+    let mut updated = state.profiles.read().clone();
+    let before = updated.len();
+    updated.retain(|profile| !profiles::same_name(&profile.name, &name));
+    if updated.len() == before {
+        return Err(VeloxError::ProfileNotFound(name));
+    }
+
+    let updated = write_profiles(path, updated).await?;
+    *state.profiles.write() = updated;
+
+    Ok(true)
+}
+
+/// Run a saved profile's request exactly like `scan_directory`
+#[tauri::command]
+pub async fn run_profile(
+    window: Window,
+    state: State<'_, VeloxState>,
+    name: String,
+) -> Result<ScanResult, VeloxError> {
+    let request = state
+        .profiles
+        .read()
+        .iter()
+        .find(|profile| profiles::same_name(&profile.name, &name))
+        .map(|profile| profile.request.clone())
+        .ok_or_else(|| VeloxError::ProfileNotFound(name.clone()))?;
+
+    tracing::info!("▶️ Running scan profile: {}", name);
+    scan_directory(window, state, request).await
+}
+
 /// Open native folder dialog and return selected path
 #[tauri::command]
 pub async fn open_folder_dialog(_window: Window) -> Result<Option<String>, VeloxError> {
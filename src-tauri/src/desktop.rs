@@ -0,0 +1,321 @@
+// VELOX CORE - Desktop Integration
+// Handing scan results over to the OS: file manager, default apps, trash, and moves
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::process::Stdio;
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::{TrashFailure, TrashResult};
+
+/// Show a file or folder selected in the platform's file manager
+pub fn reveal_in_file_manager(path: &Path) -> VeloxResult<()> {
+    if !path.exists() {
+        return Err(VeloxError::InvalidPath(path.to_string_lossy().to_string()));
+    }
+
+    reveal(path)
+}
+
+/// Open a file or folder with the platform's default handler
+pub fn open_path(path: &Path) -> VeloxResult<()> {
+    if !path.exists() {
+        return Err(VeloxError::InvalidPath(path.to_string_lossy().to_string()));
+    }
+
+    open::that(path).map_err(|e| match e.kind() {
+        io::ErrorKind::PermissionDenied => {
+            VeloxError::AccessDenied(path.to_string_lossy().to_string())
+        }
+        _ => VeloxError::io_at(path, e),
+    })
+}
+
+/// Move each path to the system trash, never deleting permanently.
+/// Paths are handled one by one so a failure doesn't abort the rest.
+pub fn move_to_trash(paths: &[String]) -> TrashResult {
+    let mut result = TrashResult {
+        trashed: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for path in paths {
+        match trash::delete(path) {
+            Ok(()) => result.trashed.push(path.clone()),
+            Err(e) => {
+                tracing::warn!("⚠️ Failed to trash {}: {}", path, e);
+                result.errors.push(TrashFailure {
+                    path: path.clone(),
+                    message: e.to_string(),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Move or rename a file or folder. Falls back to copy-then-delete when the
+/// destination is on another volume. An existing destination is only
+/// replaced when `overwrite` is set; it is set aside first and deleted once
+/// the move succeeded, or put back if the move failed.
+pub fn move_path(from: &Path, to: &Path, overwrite: bool) -> VeloxResult<()> {
+    // symlink_metadata so a dangling symlink can still be moved
+    fs::symlink_metadata(from).map_err(|e| io_error(from, e))?;
+
+    if resolve_parent(to).starts_with(resolve_parent(from)) {
+        return Err(VeloxError::InvalidArgument(format!(
+            "cannot move {} into itself",
+            from.display()
+        )));
+    }
+
+    // A case-only rename on a case-insensitive volume sees the source as the destination
+    let same_entry = matches!(
+        (fs::canonicalize(from), fs::canonicalize(to)),
+        (Ok(a), Ok(b)) if a == b
+    );
+
+    let displaced = match fs::symlink_metadata(to).ok().filter(|_| !same_entry) {
+        Some(_) if !overwrite => {
+            return Err(VeloxError::InvalidArgument(format!(
+                "{} already exists",
+                to.display()
+            )));
+        }
+        Some(_) if resolve_parent(from).starts_with(resolve_parent(to)) => {
+            return Err(VeloxError::InvalidArgument(format!(
+                "cannot replace {} with its own contents",
+                to.display()
+            )));
+        }
+        Some(_) => Some(set_aside(to)?),
+        None => None,
+    };
+
+    let moved = rename_or_copy(from, to);
+
+    if let Some(aside) = displaced {
+        if moved.is_ok() {
+            if let Err(e) = remove_entry(&aside) {
+                tracing::warn!("Could not remove replaced {}: {}", aside.display(), e);
+            }
+        } else if fs::symlink_metadata(to).is_ok() {
+            // The copy landed but the source could not be deleted; keep both
+            tracing::warn!("Kept replaced {} at {}", to.display(), aside.display());
+        } else if let Err(e) = fs::rename(&aside, to) {
+            tracing::error!(
+                "Could not restore {} from {}: {}",
+                to.display(),
+                aside.display(),
+                e
+            );
+        }
+    }
+
+    moved
+}
+
+fn rename_or_copy(from: &Path, to: &Path) -> VeloxResult<()> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if is_cross_device(&e) => {
+            tracing::debug!(
+                "Cross-device move, copying {} to {}",
+                from.display(),
+                to.display()
+            );
+            copy_then_remove(from, to)
+        }
+        Err(e) => Err(io_error(from, e)),
+    }
+}
+
+/// Rename an entry to a hidden sibling so its name is free. The sibling is
+/// on the same volume, so this is a plain rename for files and directories.
+fn set_aside(path: &Path) -> VeloxResult<PathBuf> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let aside = path.with_file_name(format!(".{}.velox-replaced-{}", name, uuid::Uuid::new_v4()));
+    fs::rename(path, &aside).map_err(|e| io_error(path, e))?;
+    Ok(aside)
+}
+
+/// Copy across volumes, then delete the source. A failed copy is cleaned up
+/// and leaves the source untouched.
+fn copy_then_remove(from: &Path, to: &Path) -> VeloxResult<()> {
+    if let Err(e) = copy_tree(from, to) {
+        let _ = remove_entry(to);
+        return Err(e);
+    }
+
+    remove_entry(from).map_err(|e| io_error(from, e))
+}
+
+/// Delete a file, symlink or directory tree
+fn remove_entry(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Absolute form of a path with symlinks resolved in its parent only, so a
+/// symlink itself is compared rather than its target
+fn resolve_parent(path: &Path) -> PathBuf {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return path.to_path_buf();
+    };
+    let parent = if parent.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        parent
+    };
+
+    fs::canonicalize(parent)
+        .map(|parent| parent.join(name))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Recursively copy a file, symlink or directory
+fn copy_tree(from: &Path, to: &Path) -> VeloxResult<()> {
+    let file_type = fs::symlink_metadata(from)
+        .map_err(|e| io_error(from, e))?
+        .file_type();
+
+    if file_type.is_dir() {
+        fs::create_dir(to).map_err(|e| io_error(to, e))?;
+        for entry in fs::read_dir(from).map_err(|e| io_error(from, e))? {
+            let entry = entry.map_err(|e| io_error(from, e))?;
+            copy_tree(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else if file_type.is_symlink() {
+        copy_symlink(from, to)
+    } else {
+        fs::copy(from, to).map(|_| ()).map_err(|e| io_error(to, e))
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> VeloxResult<()> {
+    let target = fs::read_link(from).map_err(|e| io_error(from, e))?;
+    std::os::unix::fs::symlink(target, to).map_err(|e| io_error(to, e))
+}
+
+/// Creating symlinks needs extra privileges on Windows, so copy the target
+#[cfg(not(unix))]
+fn copy_symlink(from: &Path, to: &Path) -> VeloxResult<()> {
+    fs::copy(from, to).map(|_| ()).map_err(|e| io_error(to, e))
+}
+
+/// `io::ErrorKind::CrossesDevices` is newer than our MSRV, so match the raw codes
+fn is_cross_device(error: &io::Error) -> bool {
+    // EXDEV on Linux and macOS, ERROR_NOT_SAME_DEVICE on Windows
+    let code = if cfg!(windows) { 17 } else { 18 };
+    error.raw_os_error() == Some(code)
+}
+
+fn io_error(path: &Path, error: io::Error) -> VeloxError {
+    match error.kind() {
+        io::ErrorKind::NotFound => VeloxError::InvalidPath(path.display().to_string()),
+        io::ErrorKind::PermissionDenied => VeloxError::AccessDenied(path.display().to_string()),
+        _ => VeloxError::io_at(path, error),
+    }
+}
+
+fn no_handler(path: &Path, reason: impl std::fmt::Display) -> VeloxError {
+    VeloxError::Unknown(format!(
+        "No file manager available to reveal {}: {}",
+        path.display(),
+        reason
+    ))
+}
+
+#[cfg(target_os = "windows")]
+fn reveal(path: &Path) -> VeloxResult<()> {
+    let mut select = std::ffi::OsString::from("/select,");
+    select.push(path);
+
+    // Explorer exits non-zero even on success, so only spawning is checked
+    Command::new("explorer")
+        .arg(select)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| no_handler(path, e))
+}
+
+#[cfg(target_os = "macos")]
+fn reveal(path: &Path) -> VeloxResult<()> {
+    Command::new("open")
+        .arg("-R")
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| no_handler(path, e))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal(path: &Path) -> VeloxResult<()> {
+    // The FileManager1 D-Bus interface selects the item in Nautilus, Dolphin, Nemo, etc.
+    let shown = Command::new("dbus-send")
+        .args([
+            "--session",
+            // Wait for the reply so a missing file manager service is reported as failure
+            "--print-reply",
+            "--dest=org.freedesktop.FileManager1",
+            "--type=method_call",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+        ])
+        .arg(format!("array:string:{}", file_uri(path)))
+        .arg("string:")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+
+    if shown {
+        return Ok(());
+    }
+
+    // Without D-Bus, opening the containing folder is the best we can do
+    let folder = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+
+    Command::new("xdg-open")
+        .arg(folder)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| no_handler(path, e))
+}
+
+#[cfg(not(any(target_os = "windows", unix)))]
+fn reveal(path: &Path) -> VeloxResult<()> {
+    Err(no_handler(path, "unsupported platform"))
+}
+
+/// Percent-encode an absolute path as a `file://` URI
+#[cfg(all(unix, not(target_os = "macos")))]
+fn file_uri(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let absolute = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut uri = String::from("file://");
+    for &byte in absolute.as_os_str().as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
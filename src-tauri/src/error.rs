@@ -1,7 +1,9 @@
 // VELOX CORE - Error Handling Module
 // Strict error types for robust IPC communication
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use thiserror::Error;
 
 /// Core error types for VELOX operations
@@ -10,9 +12,17 @@ pub enum VeloxError {
     #[error("IO Error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// `message` rather than `source`: thiserror treats a field named
+    /// `source` as the underlying error and requires it to implement `Error`
+    #[error("IO Error at {path}: {message}")]
+    IoAt { path: String, message: String },
+
     #[error("Scan operation cancelled by user")]
     ScanCancelled,
 
+    #[error("Scan stopped: memory use of {used} bytes passed the {limit} byte limit")]
+    MemoryLimitExceeded { used: u64, limit: u64 },
+
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
@@ -22,9 +32,30 @@ pub enum VeloxError {
     #[error("Scan already in progress for session: {0}")]
     ScanInProgress(String),
 
+    #[error("Too many concurrent scans (limit: {0})")]
+    TooManyScans(usize),
+
     #[error("No active scan found for session: {0}")]
     NoActiveScan(String),
 
+    #[error("No active watch found: {0}")]
+    NoActiveWatch(String),
+
+    #[error("No scan profile found: {0}")]
+    ProfileNotFound(String),
+
+    #[error("Scan profile already exists: {0}")]
+    ProfileExists(String),
+
+    #[error("Watch error: {0}")]
+    Watch(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Invalid pattern: {0}")]
+    InvalidPattern(String),
+
     #[error("Serialization error: {0}")]
     Serialization(String),
 
@@ -36,7 +67,7 @@ pub enum VeloxError {
 }
 
 /// Serializable error response for frontend consumption
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct ErrorResponse {
     pub code: String,
     pub message: String,
@@ -48,20 +79,35 @@ impl From<VeloxError> for ErrorResponse {
     fn from(error: VeloxError) -> Self {
         let (code, message) = match &error {
             VeloxError::Io(e) => ("IO_ERROR".to_string(), e.to_string()),
+            VeloxError::IoAt { message, .. } => ("IO_ERROR".to_string(), message.clone()),
             VeloxError::ScanCancelled => ("SCAN_CANCELLED".to_string(), error.to_string()),
+            VeloxError::MemoryLimitExceeded { .. } => ("MEMORY_LIMIT_EXCEEDED".to_string(), error.to_string()),
             VeloxError::InvalidPath(p) => ("INVALID_PATH".to_string(), format!("Invalid path: {}", p)),
             VeloxError::AccessDenied(p) => ("ACCESS_DENIED".to_string(), format!("Access denied: {}", p)),
             VeloxError::ScanInProgress(s) => ("SCAN_IN_PROGRESS".to_string(), format!("Scan already running: {}", s)),
+            VeloxError::TooManyScans(l) => ("TOO_MANY_SCANS".to_string(), format!("Concurrent scan limit reached: {}", l)),
             VeloxError::NoActiveScan(s) => ("NO_ACTIVE_SCAN".to_string(), format!("No scan found: {}", s)),
+            VeloxError::NoActiveWatch(w) => ("NO_ACTIVE_WATCH".to_string(), format!("No watch found: {}", w)),
+            VeloxError::ProfileNotFound(n) => ("PROFILE_NOT_FOUND".to_string(), format!("No profile found: {}", n)),
+            VeloxError::ProfileExists(n) => ("PROFILE_EXISTS".to_string(), format!("Profile already exists: {}", n)),
+            VeloxError::Watch(e) => ("WATCH_ERROR".to_string(), e.clone()),
+            VeloxError::InvalidArgument(a) => ("INVALID_ARGUMENT".to_string(), format!("Invalid argument: {}", a)),
+            VeloxError::InvalidPattern(p) => ("INVALID_PATTERN".to_string(), format!("Invalid pattern: {}", p)),
             VeloxError::Serialization(e) => ("SERIALIZATION_ERROR".to_string(), e.clone()),
             VeloxError::StateLock(e) => ("STATE_LOCK_ERROR".to_string(), e.clone()),
             VeloxError::Unknown(e) => ("UNKNOWN_ERROR".to_string(), e.clone()),
         };
 
+        // Point the frontend at the offending path when we know it
+        let details = match &error {
+            VeloxError::IoAt { path, .. } => path.clone(),
+            _ => format!("{:?}", error),
+        };
+
         ErrorResponse {
             code,
             message,
-            details: Some(format!("{:?}", error)),
+            details: Some(details),
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -82,11 +128,26 @@ impl Clone for VeloxError {
     fn clone(&self) -> Self {
         match self {
             Self::Io(e) => Self::Unknown(e.to_string()),
+            Self::IoAt { path, message } => Self::IoAt {
+                path: path.clone(),
+                message: message.clone(),
+            },
             Self::ScanCancelled => Self::ScanCancelled,
+            Self::MemoryLimitExceeded { used, limit } => Self::MemoryLimitExceeded {
+                used: *used,
+                limit: *limit,
+            },
             Self::InvalidPath(p) => Self::InvalidPath(p.clone()),
             Self::AccessDenied(p) => Self::AccessDenied(p.clone()),
             Self::ScanInProgress(s) => Self::ScanInProgress(s.clone()),
+            Self::TooManyScans(l) => Self::TooManyScans(*l),
             Self::NoActiveScan(s) => Self::NoActiveScan(s.clone()),
+            Self::NoActiveWatch(w) => Self::NoActiveWatch(w.clone()),
+            Self::ProfileNotFound(n) => Self::ProfileNotFound(n.clone()),
+            Self::ProfileExists(n) => Self::ProfileExists(n.clone()),
+            Self::Watch(e) => Self::Watch(e.clone()),
+            Self::InvalidArgument(a) => Self::InvalidArgument(a.clone()),
+            Self::InvalidPattern(p) => Self::InvalidPattern(p.clone()),
             Self::Serialization(e) => Self::Serialization(e.clone()),
             Self::StateLock(e) => Self::StateLock(e.clone()),
             Self::Unknown(e) => Self::Unknown(e.clone()),
@@ -94,5 +155,15 @@ impl Clone for VeloxError {
     }
 }
 
+impl VeloxError {
+    /// Wrap an IO error together with the path it happened on
+    pub fn io_at(path: impl AsRef<Path>, error: std::io::Error) -> Self {
+        Self::IoAt {
+            path: path.as_ref().display().to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
 pub type VeloxResult<T> = Result<T, VeloxError>;
 
@@ -0,0 +1,307 @@
+// VELOX CORE - Scan Estimates
+// Time-boxed sampling to predict how long a scan will take
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use walkdir::{DirEntry, WalkDir};
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::scanner::{validate_root, ScanConfig};
+use crate::types::{EstimateConfidence, MetadataLevel, ScanEstimate, ScanSession};
+
+/// Longest an estimate spends reading directories
+pub const SAMPLE_BUDGET: Duration = Duration::from_millis(500);
+
+/// Directory levels listed below each root before sampling subtrees
+const SAMPLE_LEVELS: usize = 2;
+
+/// Entries read between deadline checks inside one directory or subtree
+const CHECK_INTERVAL: u64 = 256;
+
+/// Subtrees walked per pass over the pending directories, spread evenly
+const SUBTREE_STRIDES: usize = 32;
+
+/// Fully walked subtrees needed before an extrapolation counts as medium confidence
+const MIN_SUBTREES: usize = 8;
+
+/// What the sampling pass saw
+#[derive(Default)]
+struct Sample {
+    files: u64,
+    /// Directories found below the roots
+    directories: u64,
+    /// Directories listed during the top-level pass
+    dirs_listed: u64,
+    /// The budget ran out before every pending subtree was walked
+    out_of_time: bool,
+}
+
+/// Settings shared by every read of one estimate
+struct Sampler<'a> {
+    config: &'a ScanConfig,
+    with_metadata: bool,
+    deadline: Instant,
+}
+
+/// Entry totals of the subtrees that were walked to the end
+#[derive(Default)]
+struct SubtreeTotals {
+    count: u64,
+    files: u64,
+    directories: u64,
+}
+
+/// Estimate entry counts and duration for a scan with `config`.
+///
+/// Lists the top `SAMPLE_LEVELS` levels of every root, then spends what is
+/// left of `SAMPLE_BUDGET` walking whole subtrees below them, picked evenly
+/// across the pending directories. Unwalked subtrees are assumed to be as
+/// large as the average walked one, and the duration assumes the scan runs
+/// at the rate the sample did. Only the hidden-file and depth settings are
+/// applied; globs and other filters are ignored.
+pub fn estimate_scan(config: &ScanConfig, session: &ScanSession) -> VeloxResult<ScanEstimate> {
+    for root in &config.roots {
+        validate_root(root)?;
+    }
+
+    let started = Instant::now();
+    let deadline = started + SAMPLE_BUDGET;
+    let sampler = Sampler {
+        config,
+        with_metadata: config.metadata != MetadataLevel::None,
+        deadline,
+    };
+    let mut sample = Sample::default();
+
+    // Breadth-first across all roots so a tight budget still covers each of them
+    let mut level: Vec<(PathBuf, usize)> = match config.max_depth {
+        0 => Vec::new(),
+        _ => config
+            .roots
+            .iter()
+            .map(|root| (PathBuf::from(root), 0))
+            .collect(),
+    };
+    let mut pending: Vec<(PathBuf, usize)> = Vec::new();
+    for depth in 0..SAMPLE_LEVELS.min(config.max_depth) {
+        let mut next = Vec::new();
+        let mut unread = level.into_iter();
+        for (dir, _) in unread.by_ref() {
+            if session.is_cancelled() {
+                return Err(VeloxError::ScanCancelled);
+            }
+            if Instant::now() >= deadline {
+                sample.out_of_time = true;
+                pending.push((dir, depth));
+                break;
+            }
+
+            // Children past the depth limit are counted but never descended into
+            let descend = depth + 1 < config.max_depth;
+            sampler.list_directory(&dir, depth, descend, &mut sample, &mut next);
+            if sample.out_of_time {
+                break;
+            }
+        }
+
+        // Directories the budget didn't reach are sampled as subtrees instead
+        pending.extend(unread);
+        level = next;
+        if sample.out_of_time {
+            break;
+        }
+    }
+    pending.extend(level);
+
+    let mut walked = SubtreeTotals::default();
+    let mut visited: u64 = 0;
+    for index in spread_order(pending.len()) {
+        if session.is_cancelled() {
+            return Err(VeloxError::ScanCancelled);
+        }
+        if Instant::now() >= deadline {
+            sample.out_of_time = true;
+            break;
+        }
+
+        let (dir, depth) = &pending[index];
+        let (files, directories, complete) = sampler.walk_subtree(dir, *depth);
+        sample.files += files;
+        sample.directories += directories;
+        visited += 1;
+
+        // A cut-short subtree still counts what it saw but would skew the average
+        if complete {
+            walked.count += 1;
+            walked.files += files;
+            walked.directories += directories;
+        } else {
+            sample.out_of_time = true;
+            break;
+        }
+    }
+
+    let unvisited = pending.len() as u64 - visited;
+    let elapsed = started.elapsed();
+    Ok(extrapolate(
+        &sample,
+        &walked,
+        unvisited,
+        config.roots.len() as u64,
+        elapsed,
+    ))
+}
+
+impl Sampler<'_> {
+    /// List one directory into `sample`, queueing subdirectories on `next` when
+    /// `descend` is set. Stops early and flags `out_of_time` past the deadline.
+    fn list_directory(
+        &self,
+        dir: &Path,
+        depth: usize,
+        descend: bool,
+        sample: &mut Sample,
+        next: &mut Vec<(PathBuf, usize)>,
+    ) {
+        // Unreadable directories are skipped, as the scan would skip them
+        let Ok(listing) = fs::read_dir(dir) else {
+            return;
+        };
+        sample.dirs_listed += 1;
+
+        let mut read: u64 = 0;
+        for entry in listing.flatten() {
+            read += 1;
+            if read % CHECK_INTERVAL == 0 && Instant::now() >= self.deadline {
+                sample.out_of_time = true;
+                return;
+            }
+
+            let hidden = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with('.'));
+            if hidden && !self.config.include_hidden {
+                continue;
+            }
+
+            // Pay for metadata like the scan will, so the measured rate is comparable
+            if self.with_metadata {
+                entry.metadata().ok();
+            }
+
+            match entry.file_type() {
+                Ok(file_type) if file_type.is_dir() => {
+                    sample.directories += 1;
+                    if descend {
+                        next.push((entry.path(), depth + 1));
+                    }
+                }
+                Ok(file_type) if file_type.is_file() => sample.files += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Count the files and directories below `dir`, which sits `depth` levels
+    /// under its root. The flag is false when the deadline cut the walk short.
+    fn walk_subtree(&self, dir: &Path, depth: usize) -> (u64, u64, bool) {
+        let include_hidden = self.config.include_hidden;
+        let walker = WalkDir::new(dir)
+            .min_depth(1)
+            .max_depth(self.config.max_depth.saturating_sub(depth))
+            .into_iter()
+            .filter_entry(move |entry| include_hidden || !is_hidden(entry));
+
+        let (mut files, mut directories) = (0, 0);
+        let mut read: u64 = 0;
+        for entry in walker.flatten() {
+            read += 1;
+            if read % CHECK_INTERVAL == 0 && Instant::now() >= self.deadline {
+                return (files, directories, false);
+            }
+
+            if self.with_metadata {
+                entry.metadata().ok();
+            }
+
+            if entry.file_type().is_dir() {
+                directories += 1;
+            } else if entry.file_type().is_file() {
+                files += 1;
+            }
+        }
+
+        (files, directories, true)
+    }
+}
+
+fn is_hidden(entry: &DirEntry) -> bool {
+    entry
+        .file_name()
+        .to_str()
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+/// Indices `0..len` in evenly spaced strides, so a sample cut short still
+/// spans the whole range instead of only its start
+fn spread_order(len: usize) -> impl Iterator<Item = usize> {
+    let stride = (len / SUBTREE_STRIDES).max(1);
+    (0..stride).flat_map(move |offset| (offset..len).step_by(stride))
+}
+
+/// Scale the sample up to the whole tree and time it at the sampled rate
+fn extrapolate(
+    sample: &Sample,
+    walked: &SubtreeTotals,
+    unvisited: u64,
+    roots: u64,
+    elapsed: Duration,
+) -> ScanEstimate {
+    let listed = sample.files + sample.directories;
+
+    // Average subtree when any were walked, else the average top-level listing
+    let (files_each, dirs_each) = if walked.count > 0 {
+        (
+            walked.files as f64 / walked.count as f64,
+            walked.directories as f64 / walked.count as f64,
+        )
+    } else if sample.dirs_listed > 0 {
+        (
+            sample.files as f64 / sample.dirs_listed as f64,
+            sample.directories as f64 / sample.dirs_listed as f64,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+
+    let estimated_files = sample.files + (files_each * unvisited as f64) as u64;
+    let estimated_directories = roots + sample.directories + (dirs_each * unvisited as f64) as u64;
+
+    let seconds = elapsed.as_secs_f64();
+    let estimated_duration_ms = if listed > 0 && seconds > 0.0 {
+        let per_sec = listed as f64 / seconds;
+        ((estimated_files + estimated_directories) as f64 / per_sec * 1000.0) as u64
+    } else {
+        0
+    };
+
+    let confidence = if unvisited == 0 && !sample.out_of_time {
+        EstimateConfidence::High
+    } else if walked.count >= MIN_SUBTREES.min((walked.count + unvisited) as usize) as u64 {
+        EstimateConfidence::Medium
+    } else {
+        EstimateConfidence::Low
+    };
+
+    ScanEstimate {
+        estimated_files,
+        estimated_directories,
+        estimated_duration_ms,
+        confidence,
+        sampled_entries: listed,
+        sample_ms: elapsed.as_millis() as u64,
+    }
+}
@@ -0,0 +1,526 @@
+// VELOX CORE - Result Export
+// Writers that turn a completed scan into portable files
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::{params, Connection};
+
+use crate::analysis::{tree_order, LargestFiles};
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::{ExportStats, FileEntry, ScanResult};
+
+/// Flush NDJSON output every this many lines
+const NDJSON_FLUSH_INTERVAL: u64 = 1000;
+
+/// Rows in the largest-files table of the HTML report
+const HTML_TOP_FILES: usize = 100;
+
+/// Depth rendered by the Markdown tree when the caller gives no limit
+const MARKDOWN_DEFAULT_DEPTH: usize = 4;
+
+/// Inline stylesheet so the report is a single self-contained file
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#1f2328}\
+h1{font-size:1.4rem}dl{display:grid;grid-template-columns:max-content auto;gap:.25rem 1rem}\
+dt{font-weight:600}table{border-collapse:collapse;width:100%;margin-top:1rem}\
+th,td{text-align:left;padding:.3rem .6rem;border-bottom:1px solid #d0d7de}\
+td.size{text-align:right;white-space:nowrap}td.path{font-family:monospace;word-break:break-all}";
+
+/// Column order of the CSV export
+const CSV_HEADER: [&str; 9] = [
+    "path",
+    "name",
+    "size",
+    "is_directory",
+    "is_symlink",
+    "extension",
+    "modified",
+    "created",
+    "depth",
+];
+
+/// Schema of the SQLite export; indexes are added after the rows
+const SQLITE_SCHEMA: &str = "CREATE TABLE files (
+    path TEXT NOT NULL,
+    name TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    is_dir INTEGER NOT NULL,
+    extension TEXT,
+    modified TEXT,
+    depth INTEGER NOT NULL
+);";
+
+/// Indexes for the common "largest files" and "by extension" queries
+const SQLITE_INDEXES: &str = "CREATE INDEX files_size ON files (size);
+CREATE INDEX files_extension ON files (extension);";
+
+/// Map CSV failures onto the error types the frontend already understands
+fn csv_error(error: csv::Error, output: &Path) -> VeloxError {
+    match error.into_kind() {
+        csv::ErrorKind::Io(e) => VeloxError::io_at(output, e),
+        other => VeloxError::Serialization(format!("{:?}", other)),
+    }
+}
+
+/// SQLite failures are reported against the database file
+fn sqlite_error(error: rusqlite::Error, output: &Path) -> VeloxError {
+    VeloxError::IoAt {
+        path: output.display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// Keep disk failures as IO errors instead of folding them into serialization
+fn json_error(error: serde_json::Error, output: &Path) -> VeloxError {
+    if error.is_io() {
+        VeloxError::io_at(output, error.into())
+    } else {
+        VeloxError::Serialization(error.to_string())
+    }
+}
+
+/// Write one CSV row per entry, gzip-compressed when `compress` is set.
+/// `lines_written` counts entry rows, not the header.
+pub fn write_csv(result: &ScanResult, output: &Path, compress: bool) -> VeloxResult<ExportStats> {
+    let file = CountingWriter::new(ExportFile::create(output, compress)?);
+    let mut writer = csv::Writer::from_writer(file);
+
+    writer
+        .write_record(CSV_HEADER)
+        .map_err(|e| csv_error(e, output))?;
+
+    let mut rows: u64 = 0;
+    for entry in &result.entries {
+        // Whichever representation the scan produced; millis are written as-is
+        let modified = timestamp_field(&entry.modified, entry.modified_ms);
+        let created = timestamp_field(&entry.created, entry.created_ms);
+        writer
+            .write_record([
+                entry.path.as_str(),
+                entry.name.as_str(),
+                &entry.size.to_string(),
+                &entry.is_directory.to_string(),
+                &entry.is_symlink.to_string(),
+                entry.extension.as_deref().unwrap_or(""),
+                &modified,
+                &created,
+                &entry.depth.to_string(),
+            ])
+            .map_err(|e| csv_error(e, output))?;
+        rows += 1;
+    }
+
+    let mut file = writer
+        .into_inner()
+        .map_err(|e| VeloxError::io_at(output, e.into_error()))?;
+    let compressed_bytes = file.inner.finish().map_err(|e| VeloxError::io_at(output, e))?;
+
+    tracing::info!("📤 Wrote {} CSV rows to {}", rows, output.display());
+    Ok(ExportStats {
+        lines_written: rows,
+        bytes_written: file.bytes,
+        compressed_bytes,
+    })
+}
+
+/// CSV cell for a timestamp stored either as RFC3339 or as epoch millis
+fn timestamp_field(text: &Option<String>, millis: Option<i64>) -> String {
+    match (text, millis) {
+        (Some(text), _) => text.clone(),
+        (None, Some(millis)) => millis.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Writer adapter that counts the bytes passing through it
+struct CountingWriter<W> {
+    inner: W,
+    bytes: u64,
+}
+
+impl<W> CountingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, bytes: 0 }
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Whether to gzip an export: the caller's choice, else a `.gz` output name
+pub fn wants_gzip(output: &Path, compress: Option<bool>) -> bool {
+    compress.unwrap_or_else(|| {
+        output
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+    })
+}
+
+/// File an export is written to, optionally through a gzip encoder
+enum ExportFile {
+    Plain(CountingWriter<BufWriter<File>>),
+    Gzip(GzEncoder<CountingWriter<BufWriter<File>>>),
+}
+
+impl ExportFile {
+    /// Create or truncate `output`
+    fn create(output: &Path, compress: bool) -> VeloxResult<Self> {
+        let file = File::create(output).map_err(|e| VeloxError::io_at(output, e))?;
+        let file = CountingWriter::new(BufWriter::new(file));
+        Ok(if compress {
+            Self::Gzip(GzEncoder::new(file, Compression::default()))
+        } else {
+            Self::Plain(file)
+        })
+    }
+
+    /// Flush everything to disk, ending the gzip stream if there is one.
+    /// Returns the compressed size, or `None` for plain output.
+    fn finish(&mut self) -> io::Result<Option<u64>> {
+        match self {
+            Self::Plain(file) => {
+                file.flush()?;
+                Ok(None)
+            }
+            Self::Gzip(encoder) => {
+                encoder.try_finish()?;
+                let file = encoder.get_mut();
+                file.flush()?;
+                Ok(Some(file.bytes))
+            }
+        }
+    }
+}
+
+impl Write for ExportFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(file) => file.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(file) => file.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Appends entries to an NDJSON file as they arrive. Whole lines only ever
+/// reach the file, so stopping between entries leaves valid output.
+pub struct NdjsonWriter {
+    output: PathBuf,
+    writer: CountingWriter<ExportFile>,
+    lines: u64,
+}
+
+impl NdjsonWriter {
+    /// Create or truncate `output`, gzip-compressing it when `compress` is set
+    pub fn create(output: &Path, compress: bool) -> VeloxResult<Self> {
+        Ok(Self {
+            output: output.to_path_buf(),
+            writer: CountingWriter::new(ExportFile::create(output, compress)?),
+            lines: 0,
+        })
+    }
+
+    /// Serialize one entry as a line, flushing every `NDJSON_FLUSH_INTERVAL` lines
+    pub fn write_entry(&mut self, entry: &FileEntry) -> VeloxResult<()> {
+        let mut line = serde_json::to_vec(entry).map_err(|e| json_error(e, &self.output))?;
+        line.push(b'\n');
+        self.writer
+            .write_all(&line)
+            .map_err(|e| VeloxError::io_at(&self.output, e))?;
+        self.lines += 1;
+
+        if self.lines % NDJSON_FLUSH_INTERVAL == 0 {
+            self.writer
+                .flush()
+                .map_err(|e| VeloxError::io_at(&self.output, e))?;
+        }
+        Ok(())
+    }
+
+    /// Flush everything written so far and report the totals. A gzip stream
+    /// is ended here, so nothing more can be written afterwards.
+    pub fn finish(&mut self) -> VeloxResult<ExportStats> {
+        let compressed_bytes = self
+            .writer
+            .inner
+            .finish()
+            .map_err(|e| VeloxError::io_at(&self.output, e))?;
+
+        tracing::info!(
+            "📤 Wrote {} NDJSON lines to {}",
+            self.lines,
+            self.output.display()
+        );
+        Ok(ExportStats {
+            lines_written: self.lines,
+            bytes_written: self.writer.bytes,
+            compressed_bytes,
+        })
+    }
+}
+
+/// Write one JSON-serialized entry per line, streaming straight to disk and
+/// gzip-compressing when `compress` is set
+pub fn write_ndjson(result: &ScanResult, output: &Path, compress: bool) -> VeloxResult<ExportStats> {
+    let mut writer = NdjsonWriter::create(output, compress)?;
+    for entry in &result.entries {
+        writer.write_entry(entry)?;
+    }
+    writer.finish()
+}
+
+/// Write every entry into the `files` table of a new SQLite database, replacing
+/// any file already at `output`. Rows go in through a single transaction and
+/// `size` and `extension` are indexed afterwards. Returns the rows written.
+pub fn write_sqlite(result: &ScanResult, output: &Path) -> VeloxResult<u64> {
+    match fs::remove_file(output) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(VeloxError::io_at(output, e)),
+        _ => {}
+    }
+
+    let mut db = Connection::open(output).map_err(|e| sqlite_error(e, output))?;
+    db.execute_batch(SQLITE_SCHEMA)
+        .map_err(|e| sqlite_error(e, output))?;
+
+    let tx = db.transaction().map_err(|e| sqlite_error(e, output))?;
+    let mut rows: u64 = 0;
+    {
+        let mut insert = tx
+            .prepare(
+                "INSERT INTO files (path, name, size, is_dir, extension, modified, depth)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )
+            .map_err(|e| sqlite_error(e, output))?;
+
+        for entry in &result.entries {
+            // SQLite integers are signed, so sizes past i64::MAX are clamped
+            let size = i64::try_from(entry.size).unwrap_or(i64::MAX);
+            let modified = entry
+                .modified
+                .clone()
+                .or_else(|| entry.modified_ms.map(|millis| millis.to_string()));
+            insert
+                .execute(params![
+                    entry.path,
+                    entry.name,
+                    size,
+                    entry.is_directory,
+                    entry.extension,
+                    modified,
+                    entry.depth as i64,
+                ])
+                .map_err(|e| sqlite_error(e, output))?;
+            rows += 1;
+        }
+    }
+
+    tx.execute_batch(SQLITE_INDEXES)
+        .map_err(|e| sqlite_error(e, output))?;
+    tx.commit().map_err(|e| sqlite_error(e, output))?;
+
+    tracing::info!("📤 Wrote {} SQLite rows to {}", rows, output.display());
+    Ok(rows)
+}
+
+/// Escape text for safe inclusion in HTML element content and attributes
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Write a self-contained HTML report: a summary header and the largest files
+pub fn write_html(result: &ScanResult, output: &Path) -> VeloxResult<()> {
+    let mut largest = LargestFiles::new(HTML_TOP_FILES);
+    for entry in result.entries.iter().filter(|e| e.is_file) {
+        if largest.accepts(entry.size) {
+            largest.push(entry.clone());
+        }
+    }
+    let largest = largest.into_sorted_vec();
+
+    let file = File::create(output).map_err(|e| VeloxError::io_at(output, e))?;
+    let mut writer = BufWriter::new(file);
+    let root = escape_html(&result.root_path);
+
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\"><head><meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>VELOX scan report - {}</title>", root)?;
+    writeln!(writer, "<style>{}</style></head><body>", HTML_STYLE)?;
+    writeln!(writer, "<h1>Scan report: {}</h1>", root)?;
+
+    writeln!(writer, "<dl>")?;
+    let summary = [
+        ("Root", root.clone()),
+        ("Files", result.total_files.to_string()),
+        ("Directories", result.total_directories.to_string()),
+        ("Total size", escape_html(&result.total_size_formatted)),
+        ("Duration", format!("{} ms", result.duration_ms)),
+        ("Completed", escape_html(&result.completed_at)),
+        ("Errors", result.errors.len().to_string()),
+    ];
+    for (label, value) in summary {
+        writeln!(writer, "<dt>{}</dt><dd>{}</dd>", label, value)?;
+    }
+    writeln!(writer, "</dl>")?;
+
+    writeln!(writer, "<h2>Largest files</h2>")?;
+    writeln!(
+        writer,
+        "<table><thead><tr><th>#</th><th>Size</th><th>Path</th></tr></thead><tbody>"
+    )?;
+    for (rank, entry) in largest.iter().enumerate() {
+        writeln!(
+            writer,
+            "<tr><td>{}</td><td class=\"size\">{}</td><td class=\"path\">{}</td></tr>",
+            rank + 1,
+            escape_html(&entry.size_formatted),
+            escape_html(&entry.path)
+        )?;
+    }
+    writeln!(writer, "</tbody></table>")?;
+    writeln!(writer, "</body></html>")?;
+
+    writer.flush().map_err(|e| VeloxError::io_at(output, e))?;
+
+    tracing::info!(
+        "📤 Wrote HTML report with {} files to {}",
+        largest.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Backslash-escape characters that Markdown would otherwise interpret
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Render one entry and, below the depth cap, its children
+fn write_tree_node<W: Write>(
+    writer: &mut W,
+    entry: &FileEntry,
+    children: &HashMap<&Path, Vec<&FileEntry>>,
+    max_depth: usize,
+    lines: &mut u64,
+) -> io::Result<()> {
+    let indent = "  ".repeat(entry.depth);
+    let name = escape_markdown(&entry.name);
+
+    if entry.is_directory {
+        // Directory sizes are only meaningful once aggregated
+        match entry.children_count {
+            Some(_) => writeln!(
+                writer,
+                "{}- **{}/** ({})",
+                indent, name, entry.size_formatted
+            )?,
+            None => writeln!(writer, "{}- **{}/**", indent, name)?,
+        }
+    } else {
+        writeln!(writer, "{}- {} ({})", indent, name, entry.size_formatted)?;
+    }
+    *lines += 1;
+
+    let Some(kids) = children.get(Path::new(&entry.path)) else {
+        return Ok(());
+    };
+
+    if entry.depth >= max_depth {
+        writeln!(writer, "{}  - … {} more", indent, kids.len())?;
+        *lines += 1;
+        return Ok(());
+    }
+
+    for child in kids {
+        write_tree_node(writer, child, children, max_depth, lines)?;
+    }
+    Ok(())
+}
+
+/// Write an indented Markdown bullet tree rebuilt from entry paths.
+/// Directories deeper than `max_depth` are collapsed into a "… N more" line.
+pub fn write_markdown_tree(
+    result: &ScanResult,
+    output: &Path,
+    max_depth: Option<usize>,
+) -> VeloxResult<ExportStats> {
+    let max_depth = max_depth.unwrap_or(MARKDOWN_DEFAULT_DEPTH);
+
+    let mut roots: Vec<&FileEntry> = Vec::new();
+    let mut children: HashMap<&Path, Vec<&FileEntry>> = HashMap::new();
+    for entry in &result.entries {
+        match Path::new(&entry.path).parent() {
+            Some(parent) if entry.depth > 0 => children.entry(parent).or_default().push(entry),
+            _ => roots.push(entry),
+        }
+    }
+    roots.sort_by(|a, b| a.path.cmp(&b.path));
+    for kids in children.values_mut() {
+        kids.sort_by(tree_order);
+    }
+
+    let file = File::create(output).map_err(|e| VeloxError::io_at(output, e))?;
+    let mut writer = CountingWriter::new(BufWriter::new(file));
+
+    writeln!(writer, "# {}", escape_markdown(&result.root_path))?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{} files, {} directories, {}",
+        result.total_files, result.total_directories, result.total_size_formatted
+    )?;
+    writeln!(writer)?;
+    let mut lines: u64 = 4;
+
+    for root in roots {
+        write_tree_node(&mut writer, root, &children, max_depth, &mut lines)?;
+    }
+
+    writer.flush().map_err(|e| VeloxError::io_at(output, e))?;
+
+    tracing::info!("📤 Wrote {} Markdown lines to {}", lines, output.display());
+    Ok(ExportStats {
+        lines_written: lines,
+        bytes_written: writer.bytes,
+        compressed_bytes: None,
+    })
+}
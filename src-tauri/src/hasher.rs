@@ -0,0 +1,196 @@
+// VELOX CORE - Content Hashing
+// Streaming file digests that never load whole files into memory
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Duration;
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::{HashAlgorithm, VerifyEntry, VerifyReport, VerifyStatus};
+
+/// Read buffer size used when streaming file contents
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Delay before the first retry; doubles on each further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(50);
+
+/// Feed a file to `update` in 64KB chunks
+fn stream_file(path: &Path, mut update: impl FnMut(&[u8])) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        update(&buffer[..read]);
+    }
+}
+
+/// Compute the SHA-256 hex digest of a file, reading it in 64KB chunks
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    stream_file(path, |chunk| hasher.update(chunk))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compute the hex digest of a file with the chosen algorithm
+pub fn digest_file(path: &Path, algorithm: HashAlgorithm) -> io::Result<String> {
+    match algorithm {
+        HashAlgorithm::Sha256 => sha256_file(path),
+        HashAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            stream_file(path, |chunk| {
+                hasher.update(chunk);
+            })?;
+            Ok(hasher.finalize().to_hex().to_string())
+        }
+        HashAlgorithm::Md5 => {
+            let mut hasher = Md5::new();
+            stream_file(path, |chunk| hasher.update(chunk))?;
+            Ok(format!("{:x}", hasher.finalize()))
+        }
+    }
+}
+
+/// Hash a single file on demand, mapping failures onto frontend error codes
+pub fn hash_file(path: &Path, algorithm: HashAlgorithm) -> VeloxResult<String> {
+    if path.is_dir() {
+        return Err(VeloxError::InvalidPath(format!(
+            "{} is not a file",
+            path.display()
+        )));
+    }
+
+    digest_file(path, algorithm).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => VeloxError::InvalidPath(path.display().to_string()),
+        io::ErrorKind::PermissionDenied => VeloxError::AccessDenied(path.display().to_string()),
+        _ => VeloxError::io_at(path, e),
+    })
+}
+
+/// Errors that will not go away by trying again
+fn is_permanent(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied | io::ErrorKind::InvalidInput
+    )
+}
+
+/// Hash a file, retrying transient failures up to `retries` times with
+/// exponential backoff. Useful on network mounts that drop reads now and then.
+/// Each attempt reads the file on the blocking pool, so hashing large media
+/// never holds up a runtime worker.
+pub async fn digest_file_with_retry(
+    path: &Path,
+    algorithm: HashAlgorithm,
+    retries: u8,
+) -> io::Result<String> {
+    let mut attempt: u8 = 0;
+    loop {
+        let owned = path.to_path_buf();
+        let result = tokio::task::spawn_blocking(move || digest_file(&owned, algorithm))
+            .await
+            .unwrap_or_else(|e| Err(io::Error::new(io::ErrorKind::Other, e)));
+
+        match result {
+            Err(e) if attempt < retries && !is_permanent(&e) => {
+                let delay = RETRY_BASE_DELAY * 2u32.pow(attempt.into());
+                attempt += 1;
+                tracing::debug!(
+                    "Retrying hash of {} in {:?} (attempt {}/{}): {}",
+                    path.display(),
+                    delay,
+                    attempt,
+                    retries,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Parse one `path,sha256` manifest line. The digest follows the last comma,
+/// so paths may themselves contain commas.
+fn parse_manifest_line(line: &str, number: usize) -> VeloxResult<(String, String)> {
+    let malformed =
+        |reason: &str| VeloxError::InvalidArgument(format!("manifest line {}: {}", number, reason));
+
+    let (path, digest) = line
+        .rsplit_once(',')
+        .ok_or_else(|| malformed("expected `path,sha256`"))?;
+    let (path, digest) = (path.trim(), digest.trim());
+
+    if path.is_empty() {
+        return Err(malformed("missing path"));
+    }
+    if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(malformed("sha256 must be 64 hex characters"));
+    }
+
+    Ok((path.to_string(), digest.to_ascii_lowercase()))
+}
+
+/// Recompute the digest of every file listed in a manifest and compare.
+/// Relative paths are resolved against the manifest's directory; blank lines
+/// and lines starting with `#` are skipped. The whole manifest is validated
+/// before any file is hashed.
+pub fn verify_manifest(manifest: &Path) -> VeloxResult<VerifyReport> {
+    let content = fs::read_to_string(manifest).map_err(|e| {
+        VeloxError::InvalidArgument(format!(
+            "cannot read manifest {}: {}",
+            manifest.display(),
+            e
+        ))
+    })?;
+
+    let listed = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| parse_manifest_line(line, number))
+        .collect::<VeloxResult<Vec<_>>>()?;
+
+    let base = manifest.parent().unwrap_or(Path::new(""));
+    let mut report = VerifyReport {
+        entries: Vec::with_capacity(listed.len()),
+        matched: 0,
+        mismatched: 0,
+        missing: 0,
+        unreadable: 0,
+    };
+
+    for (path, expected) in listed {
+        let (status, actual, error) = match sha256_file(&base.join(&path)) {
+            Ok(actual) if actual == expected => (VerifyStatus::Match, Some(actual), None),
+            Ok(actual) => (VerifyStatus::Mismatch, Some(actual), None),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (VerifyStatus::Missing, None, None),
+            Err(e) => (VerifyStatus::Unreadable, None, Some(e.to_string())),
+        };
+
+        match status {
+            VerifyStatus::Match => report.matched += 1,
+            VerifyStatus::Mismatch => report.mismatched += 1,
+            VerifyStatus::Missing => report.missing += 1,
+            VerifyStatus::Unreadable => report.unreadable += 1,
+        }
+
+        report.entries.push(VerifyEntry {
+            path,
+            expected,
+            actual,
+            status,
+            error,
+        });
+    }
+
+    Ok(report)
+}
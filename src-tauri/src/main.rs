@@ -6,38 +6,131 @@
     windows_subsystem = "windows"
 )]
 
+mod analysis;
+mod checkpoint;
+mod classify;
 mod commands;
+mod desktop;
 mod error;
+mod estimate;
+mod export;
+mod hasher;
+mod logs;
+mod mime;
+mod preview;
+mod profiles;
 mod scanner;
+mod schema;
+mod sink;
 mod state;
+mod system;
 mod types;
+mod validation;
+mod walker;
+mod watcher;
 
-use state::VeloxState;
-use tauri::Manager;
+use logs::{BufferLayer, LogBuffer, LOG_BUFFER_CAPACITY};
+use state::{VeloxConfig, VeloxState};
+use std::time::Duration;
+use tauri::{Manager, RunEvent};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn main() {
-    // Initialize tracing for structured logging
+    // Initialize tracing for structured logging, keeping recent lines for the UI
+    let logs = LogBuffer::new(LOG_BUFFER_CAPACITY);
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::new(
             std::env::var("RUST_LOG").unwrap_or_else(|_| "velox_core=debug,info".into()),
         ))
         .with(tracing_subscriber::fmt::layer())
+        .with(BufferLayer::new(logs.clone()))
         .init();
 
     tracing::info!("🚀 VELOX CORE Engine Starting...");
 
     tauri::Builder::default()
-        .manage(VeloxState::new())
+        .manage(VeloxState::new(logs))
         .invoke_handler(tauri::generate_handler![
             commands::scan_directory,
+            commands::scan_to_ndjson_file,
+            commands::resume_scan_from_checkpoint,
+            commands::validate_scan_request,
+            commands::estimate_scan,
+            commands::find_duplicates,
+            commands::find_similar_images,
+            commands::find_largest_files,
+            commands::find_largest_directories,
+            commands::find_recently_modified,
+            commands::analyze_by_extension,
+            commands::analyze_by_category,
+            commands::analyze_file_ages,
+            commands::compute_directory_size,
+            commands::count_matching,
+            commands::verify_checksums,
+            commands::hash_file,
             commands::cancel_scan,
+            commands::cancel_all_scans,
+            commands::pause_scan,
+            commands::resume_scan,
             commands::get_scan_status,
+            commands::list_active_scans,
+            commands::get_file_info,
+            commands::read_text_preview,
+            commands::reveal_in_file_manager,
+            commands::open_path,
+            commands::move_to_trash,
+            commands::move_path,
+            commands::copy_to_clipboard,
+            commands::watch_directory,
+            commands::stop_watch,
+            commands::get_scan_history,
+            commands::get_scan_result,
+            commands::query_scan_entries,
+            commands::scan_with_query,
+            commands::get_scan_tree,
+            commands::diff_scans,
+            commands::rescan_subtree,
+            commands::clear_scan_results,
+            commands::export_scan_csv,
+            commands::export_scan_ndjson,
+            commands::export_scan_html,
+            commands::export_scan_tree_markdown,
+            commands::export_scan_sqlite,
             commands::get_system_info,
+            commands::list_volumes,
+            commands::get_path_disk_usage,
             commands::heartbeat,
+            commands::get_recent_logs,
+            commands::get_type_schemas,
+            commands::get_config,
+            commands::update_config,
+            commands::save_profile,
+            commands::list_profiles,
+            commands::delete_profile,
+            commands::run_profile,
             commands::open_folder_dialog,
         ])
         .setup(|app| {
+            // Restore persisted configuration, falling back to defaults
+            if let Some(path) = commands::config_file_path(&app.handle()) {
+                let state = app.state::<VeloxState>();
+                *state.config.write() = VeloxConfig::load_from_disk(&path);
+            }
+            if let Some(path) = commands::profiles_file_path(&app.handle()) {
+                let state = app.state::<VeloxState>();
+                *state.profiles.write() = profiles::load_profiles(&path);
+            }
+
+            // Periodically drop sessions whose scan stopped making progress
+            let handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let mut sweep = tokio::time::interval(SESSION_SWEEP_INTERVAL);
+                loop {
+                    sweep.tick().await;
+                    handle.state::<VeloxState>().reap_stale_scans();
+                }
+            });
+
             tracing::info!("✅ VELOX CORE Initialized Successfully");
             
             // Emit ready event to frontend
@@ -49,7 +142,35 @@ fn main() {
             
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("Failed to run VELOX CORE");
+        .build(tauri::generate_context!())
+        .expect("Failed to build VELOX CORE")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                shutdown(&app.state::<VeloxState>());
+            }
+        });
+}
+
+/// How often active sessions are checked against `scan_timeout_ms`
+const SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest we wait for cancelled scans to release their file handles
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Cancel in-flight scans and give them a moment to finish cleanup
+fn shutdown(state: &VeloxState) {
+    let cancelled = state.cancel_all();
+    if state.active_scan_count() == 0 {
+        return;
+    }
+
+    tracing::info!("🛑 Cancelled {} active scan(s) before exit", cancelled);
+    if !state.wait_for_scans(SHUTDOWN_TIMEOUT) {
+        tracing::warn!(
+            "⚠️ {} scan(s) still running after {:?}, exiting anyway",
+            state.active_scan_count(),
+            SHUTDOWN_TIMEOUT
+        );
+    }
 }
 
@@ -0,0 +1,20 @@
+// VELOX CORE - Content Type Detection
+// MIME sniffing from magic bytes, independent of file extensions
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Bytes read from the start of a file; enough for every signature `infer` checks
+/// at a fixed offset, including tar's header at byte 257
+const SNIFF_BYTES: u64 = 512;
+
+/// Detect a file's MIME type from its leading bytes.
+/// Returns `None` when the file can't be read or the signature is unknown.
+pub fn sniff_mime(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut prefix = Vec::with_capacity(SNIFF_BYTES as usize);
+    file.take(SNIFF_BYTES).read_to_end(&mut prefix).ok()?;
+
+    infer::get(&prefix).map(|kind| kind.mime_type().to_string())
+}
@@ -0,0 +1,114 @@
+// VELOX CORE - Text Previews
+// Bounded reads of file heads for the inline viewer
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::TextPreview;
+
+/// Most bytes a preview will ever read, whatever the caller asks for
+pub const MAX_PREVIEW_BYTES: usize = 1024 * 1024;
+
+/// Share of undecodable bytes above which text is treated as binary.
+/// Keeps a stray Latin-1 byte in an otherwise UTF-8 file previewable.
+const MAX_INVALID_RATIO: f64 = 0.05;
+
+/// Read the head of a file as text. `max_bytes` is clamped to
+/// `MAX_PREVIEW_BYTES`; binary content comes back with empty `content`.
+pub fn read_text_preview(path: &Path, max_bytes: usize) -> VeloxResult<TextPreview> {
+    if path.is_dir() {
+        return Err(VeloxError::InvalidPath(format!(
+            "{} is not a file",
+            path.display()
+        )));
+    }
+
+    let limit = max_bytes.min(MAX_PREVIEW_BYTES);
+    let (mut bytes, truncated) = read_head(path, limit).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => VeloxError::InvalidPath(path.display().to_string()),
+        io::ErrorKind::PermissionDenied => VeloxError::AccessDenied(path.display().to_string()),
+        _ => VeloxError::io_at(path, e),
+    })?;
+
+    if looks_binary(&bytes, truncated) {
+        return Ok(TextPreview {
+            content: String::new(),
+            truncated,
+            is_binary: true,
+        });
+    }
+
+    // Don't render the character the limit cut in half as U+FFFD
+    if truncated {
+        bytes.truncate(complete_len(&bytes));
+    }
+
+    Ok(TextPreview {
+        content: String::from_utf8_lossy(&bytes).into_owned(),
+        truncated,
+        is_binary: false,
+    })
+}
+
+/// Up to `limit` leading bytes, and whether the file holds more
+fn read_head(path: &Path, limit: usize) -> io::Result<(Vec<u8>, bool)> {
+    let file = File::open(path)?;
+    let mut bytes = Vec::with_capacity(limit.min(64 * 1024));
+    // One byte past the limit tells us whether anything was cut off
+    file.take(limit as u64 + 1).read_to_end(&mut bytes)?;
+
+    let truncated = bytes.len() > limit;
+    bytes.truncate(limit);
+    Ok((bytes, truncated))
+}
+
+/// NUL bytes never appear in text; otherwise count bytes that aren't valid
+/// UTF-8. A sequence cut short by truncation doesn't count against the file.
+fn looks_binary(bytes: &[u8], truncated: bool) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+
+    let mut invalid = 0usize;
+    let mut rest = bytes;
+    while let Err(e) = std::str::from_utf8(rest) {
+        match e.error_len() {
+            Some(len) => {
+                invalid += len;
+                rest = &rest[e.valid_up_to() + len..];
+            }
+            // Incomplete sequence at the very end
+            None => {
+                if !truncated {
+                    invalid += rest.len() - e.valid_up_to();
+                }
+                break;
+            }
+        }
+    }
+
+    !bytes.is_empty() && invalid as f64 / bytes.len() as f64 > MAX_INVALID_RATIO
+}
+
+/// Length of `bytes` without a multi-byte UTF-8 sequence left incomplete at the end
+fn complete_len(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(3) {
+        let start = bytes.len() - back;
+        let lead = bytes[start];
+        if lead & 0xC0 == 0x80 {
+            // Continuation byte; keep looking for the lead
+            continue;
+        }
+
+        let needed = match lead {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1,
+        };
+        return if needed > back { start } else { bytes.len() };
+    }
+    bytes.len()
+}
@@ -0,0 +1,71 @@
+// VELOX CORE - Scan Profiles
+// Named scan requests saved for reuse, persisted next to the configuration
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::ScanProfile;
+
+/// Read saved profiles. Missing or malformed files yield no profiles
+/// instead of failing startup.
+pub fn load_profiles(path: &Path) -> Vec<ScanProfile> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tracing::debug!("No saved profiles at {}", path.display());
+            return Vec::new();
+        }
+        Err(e) => {
+            tracing::warn!("⚠️ Failed to read profiles {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        tracing::warn!(
+            "⚠️ Malformed profiles {}, ignoring them: {}",
+            path.display(),
+            e
+        );
+        Vec::new()
+    })
+}
+
+/// Write every profile as JSON, creating the parent directory if needed
+pub fn save_profiles(path: &Path, profiles: &[ScanProfile]) -> VeloxResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| VeloxError::io_at(parent, e))?;
+    }
+
+    let json = serde_json::to_string_pretty(profiles)
+        .map_err(|e| VeloxError::Serialization(e.to_string()))?;
+    fs::write(path, json).map_err(|e| VeloxError::io_at(path, e))?;
+
+    tracing::debug!("Saved {} profiles to {}", profiles.len(), path.display());
+    Ok(())
+}
+
+/// Profile names are compared ignoring case, so "Downloads" and "downloads"
+/// can't both exist
+pub fn same_name(a: &str, b: &str) -> bool {
+    a.to_lowercase() == b.to_lowercase()
+}
+
+/// The trimmed name for a new profile, rejecting blank and taken names
+pub fn check_new_name(name: &str, existing: &[ScanProfile]) -> VeloxResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(VeloxError::InvalidArgument(
+            "profile name must not be empty".to_string(),
+        ));
+    }
+    if existing
+        .iter()
+        .any(|profile| same_name(&profile.name, name))
+    {
+        return Err(VeloxError::ProfileExists(name.to_string()));
+    }
+
+    Ok(name.to_string())
+}
@@ -1,18 +1,86 @@
 // VELOX CORE - High-Performance Directory Scanner
 // Async recursive scanning with real-time progress streaming
 
-use std::path::Path;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use chrono::Utc;
-use human_bytes::human_bytes;
+use bloomfilter::Bloom;
+use chrono::{DateTime, Utc};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use parking_lot::Mutex;
+use regex::Regex;
+use sysinfo::System;
 use tauri::Window;
-use tokio::sync::mpsc;
-use walkdir::WalkDir;
+use thread_priority::{set_current_thread_priority, ThreadPriority};
+use tokio::sync::{Notify, Semaphore};
+use tokio::task::{JoinHandle, JoinSet};
 
+use crate::analysis::{
+    AgeHistogram, CategoryTotals, DepthHistogram, ExtensionTotals, LargestFiles, NewestFiles,
+};
+use crate::checkpoint::ScanCheckpoint;
 use crate::error::{VeloxError, VeloxResult};
-use crate::types::{FileEntry, ScanProgress, ScanResult, ScanSession, ScanStatus};
+use crate::hasher::digest_file_with_retry;
+use crate::mime::sniff_mime;
+use crate::sink::ProgressSink;
+use crate::state::VeloxConfig;
+use crate::types::{
+    format_bytes, ByteUnit, DedupMode, EntryKindFilter, FileEntry, HashAlgorithm, MetadataLevel,
+    RootSummary, ScanBatch, ScanErrorEntry, ScanPhase, ScanPhaseEvent, ScanPriority, ScanProgress,
+    ScanRequest, ScanResult, ScanSession, ScanStatus, TimestampFormat,
+};
+use crate::walker::{
+    io_error_entry, normalize_extensions, walk_gitignore, walk_parallel, walk_sequential,
+    WalkFilter, WalkItem, WalkIter, WalkOptions, WalkedEntry, ACCESS_DENIED_KIND,
+};
+
+/// How often a paused scan rechecks its pause and cancel flags
+const PAUSE_POLL_INTERVAL_MS: u64 = 100;
+
+/// Budget charged per entry for its metadata read when throttling
+const METADATA_READ_BYTES: u64 = 4096;
+
+/// Walked items between cooperative yields to the async runtime
+const DEFAULT_YIELD_INTERVAL: usize = 256;
+
+/// Largest yield interval for low-priority scans, so they give way more often
+const LOW_PRIORITY_YIELD_INTERVAL: usize = 32;
+
+/// Next entry id handed out when a scan asks for ids. Shared by every scan so
+/// subtree rescans spliced into a result never reuse an id.
+static NEXT_ENTRY_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Walked items between refreshes of the session's activity timestamp
+const ACTIVITY_INTERVAL: u64 = 256;
+
+/// Queued progress updates before the oldest periodic one is dropped
+const DEFAULT_PROGRESS_CAPACITY: usize = 100;
+
+/// Minimum time between checkpoint writes for finished subtrees
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Target false-positive rate of the size filter in `DedupMode::Approximate`
+const DEDUP_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Files the size filter is built for when the total wasn't counted first.
+/// Bigger trees still work; the false-positive rate just rises.
+const DEDUP_BLOOM_CAPACITY: usize = 1_000_000;
+
+/// How often the memory guard samples the process's resident memory
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Directories pruned by `skip_system_dirs` unless `skip_dir_names` overrides them
+pub const SYSTEM_DIR_NAMES: [&str; 6] = [
+    ".git",
+    "node_modules",
+    "target",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+    ".Trash",
+];
 
 /// Scanner configuration
 #[derive(Debug, Clone)]
@@ -20,7 +88,138 @@ pub struct ScanConfig {
     pub max_depth: usize,
     pub include_hidden: bool,
     pub follow_symlinks: bool,
+    /// When following symlinks, count each canonical target only once so
+    /// symlink farms inside the root don't inflate the totals
+    pub dedupe_symlink_targets: bool,
     pub progress_interval_ms: u64,
+    /// Periodic progress updates queued for the emitter before the oldest is
+    /// dropped. Status changes are never dropped and don't count toward it.
+    pub progress_channel_capacity: usize,
+    /// Only files matching at least one of these globs are collected
+    pub include_globs: Vec<String>,
+    /// Entries matching any of these globs are skipped, directories are not descended into
+    pub exclude_globs: Vec<String>,
+    /// Run a cheap counting pass first so progress_percent is meaningful
+    pub estimate_total: bool,
+    /// Emit entries in `velox:scan:batch` events of this size instead of
+    /// collecting them into `ScanResult.entries`
+    pub stream_batch_size: Option<usize>,
+    /// Stop walking once this many entries have been collected and mark the
+    /// result truncated. In streaming mode streamed entries count toward the
+    /// cap even though none are retained; ranking and grouping modes ignore it.
+    pub max_entries: Option<usize>,
+    /// Kinds of entries to return. Totals, progress and the walk itself are
+    /// unaffected. When aggregating collected entries the filter is applied
+    /// afterwards so directory sizes still include their files, and
+    /// `max_entries` then counts entries before filtering.
+    pub entry_filter: EntryKindFilter,
+    /// Per-entry metadata to read. `None` skips the stat call entirely, which
+    /// makes walks much faster on slow or network filesystems but leaves every
+    /// size at 0; `Basic` keeps sizes but drops timestamps. Totals only reflect
+    /// what was actually read.
+    pub metadata: MetadataLevel,
+    /// Representation of entry timestamps; RFC3339 strings unless asked otherwise
+    pub timestamp_format: TimestampFormat,
+    /// Units of every formatted size in the result and progress updates
+    pub byte_unit: ByteUnit,
+    /// Fill Unix mode bits and owner IDs on every entry; no effect on Windows
+    /// or without metadata
+    pub read_permissions: bool,
+    /// Flag regular files that take less space on disk than their size (Unix only)
+    pub detect_sparse: bool,
+    /// Also list symlinks with a missing target in `errors`; their entries
+    /// are flagged either way
+    pub report_broken_symlinks: bool,
+    /// Only collect zero-byte regular files; totals still cover the whole tree
+    pub only_empty_files: bool,
+    /// Fill `children_count` and recursive sizes for directory entries
+    pub aggregate_dir_sizes: bool,
+    /// Compute a `hash_algorithm` digest for every regular file
+    pub hash_files: bool,
+    /// Skip hashing files larger than this many bytes
+    pub hash_max_bytes: Option<u64>,
+    /// Digest algorithm for `hash_files`
+    pub hash_algorithm: HashAlgorithm,
+    /// Extra attempts for transient IO errors while hashing
+    pub io_retries: u8,
+    /// `Approximate` pre-walks sizes and only collects regular files whose
+    /// size was probably seen more than once; see `DedupMode`
+    pub dedup_memory_mode: DedupMode,
+    /// Pace disk reads to roughly this rate. Each entry is charged a small
+    /// fixed cost for its metadata and hashed files their full size, so the
+    /// cap is most meaningful together with `hash_files`.
+    pub max_bytes_per_sec: Option<u64>,
+    /// Sniff the MIME type of every regular file from its leading bytes
+    pub detect_mime: bool,
+    /// Walk directories on a thread pool with jwalk instead of walkdir
+    pub parallel: bool,
+    /// Worker threads used by the parallel walker
+    pub threads: usize,
+    /// Walk several roots at the same time, at most `threads` at once. Modes
+    /// that keep state across roots (ranking, grouping, checkpoints, symlink
+    /// dedupe, a rate cap) still walk them one after another.
+    pub concurrent_roots: bool,
+    /// Skip regular files smaller than this many bytes
+    pub min_size: Option<u64>,
+    /// Skip regular files larger than this many bytes
+    pub max_size: Option<u64>,
+    /// When true, files skipped by the size or time filters still count toward
+    /// `total_files`/`total_size`; otherwise totals only reflect returned entries
+    pub count_filtered: bool,
+    /// Skip regular files last modified before this instant
+    pub modified_after: Option<DateTime<Utc>>,
+    /// Skip regular files last modified after this instant
+    pub modified_before: Option<DateTime<Utc>>,
+    /// Only collect files with one of these extensions (lowercase, no dot)
+    pub include_extensions: Option<Vec<String>>,
+    /// Never collect files with these extensions; wins over `include_extensions`
+    pub exclude_extensions: Option<Vec<String>>,
+    /// Only collect files whose name matches this regular expression
+    pub name_regex: Option<String>,
+    /// Only collect files whose name contains this text, ignoring case
+    pub name_contains: Option<String>,
+    /// Apply the name filters to directories too; non-matching directories are not descended into
+    pub filter_directory_names: bool,
+    /// Never descend into version-control, dependency, build and trash
+    /// directories, matched by name ignoring case
+    pub skip_system_dirs: bool,
+    /// Names pruned by `skip_system_dirs` instead of `SYSTEM_DIR_NAMES`; empty keeps the defaults
+    pub skip_dir_names: Vec<String>,
+    /// Yield to the async runtime after this many walked items so a long walk
+    /// doesn't starve other tasks; 0 never yields
+    pub yield_interval: usize,
+    /// Keep only the N largest regular files instead of every entry
+    pub largest_files: Option<usize>,
+    /// Keep only the N most recently modified regular files instead of every entry
+    pub newest_files: Option<usize>,
+    /// Aggregate regular files by extension instead of collecting entries
+    pub group_by_extension: bool,
+    /// Bucket regular files by modified time instead of collecting entries
+    pub group_by_age: bool,
+    /// Aggregate regular files by file category instead of collecting entries
+    pub group_by_category: bool,
+    /// Keep only the session totals; no entries are collected or streamed
+    pub totals_only: bool,
+    /// Skip entries ignored by `.gitignore`/`.ignore` files; takes precedence over `parallel`
+    pub respect_gitignore: bool,
+    /// Roots combined into a single result; empty means the session root
+    pub roots: Vec<String>,
+    /// Record finished top-level subtrees here so an interrupted scan can be
+    /// resumed. Only used when collecting or streaming entries; the file is
+    /// removed once the scan finishes.
+    pub checkpoint_path: Option<String>,
+    /// Checkpoint state to continue from; finished subtrees are skipped
+    pub checkpoint: Option<ScanCheckpoint>,
+    /// Cancel the scan with `MemoryLimitExceeded` once the process's resident
+    /// memory passes this many bytes
+    pub memory_limit_bytes: Option<u64>,
+    /// Only walk this directory below the root, plus the directories leading
+    /// down to it, which are still collected
+    pub subtree: Option<PathBuf>,
+    /// Number entries from a process-wide counter; otherwise ids stay empty
+    pub generate_ids: bool,
+    /// `Low` walks on its own thread at the lowest OS priority
+    pub priority: ScanPriority,
 }
 
 impl Default for ScanConfig {
@@ -29,80 +228,795 @@ impl Default for ScanConfig {
             max_depth: 100,
             include_hidden: false,
             follow_symlinks: false,
+            dedupe_symlink_targets: false,
             progress_interval_ms: 50,
+            progress_channel_capacity: DEFAULT_PROGRESS_CAPACITY,
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            estimate_total: false,
+            stream_batch_size: None,
+            max_entries: None,
+            entry_filter: EntryKindFilter::default(),
+            metadata: MetadataLevel::default(),
+            timestamp_format: TimestampFormat::default(),
+            byte_unit: ByteUnit::default(),
+            read_permissions: false,
+            detect_sparse: false,
+            report_broken_symlinks: false,
+            only_empty_files: false,
+            aggregate_dir_sizes: false,
+            hash_files: false,
+            hash_max_bytes: None,
+            hash_algorithm: HashAlgorithm::default(),
+            io_retries: 2,
+            dedup_memory_mode: DedupMode::default(),
+            max_bytes_per_sec: None,
+            detect_mime: false,
+            parallel: false,
+            threads: num_cpus::get(),
+            concurrent_roots: false,
+            min_size: None,
+            max_size: None,
+            count_filtered: false,
+            modified_after: None,
+            modified_before: None,
+            include_extensions: None,
+            exclude_extensions: None,
+            name_regex: None,
+            name_contains: None,
+            filter_directory_names: false,
+            skip_system_dirs: false,
+            skip_dir_names: Vec::new(),
+            yield_interval: DEFAULT_YIELD_INTERVAL,
+            largest_files: None,
+            newest_files: None,
+            group_by_extension: false,
+            group_by_age: false,
+            group_by_category: false,
+            totals_only: false,
+            respect_gitignore: false,
+            roots: Vec::new(),
+            checkpoint_path: None,
+            checkpoint: None,
+            memory_limit_bytes: None,
+            subtree: None,
+            generate_ids: false,
+            priority: ScanPriority::Normal,
+        }
+    }
+}
+
+impl ScanConfig {
+    /// Defaults plus the app settings, for scans that aren't built from a request
+    pub fn from_app_config(app_config: &VeloxConfig) -> Self {
+        Self {
+            progress_interval_ms: app_config.progress_emit_interval_ms,
+            byte_unit: app_config.byte_unit,
+            memory_limit_bytes: Some(app_config.memory_limit_bytes).filter(|limit| *limit > 0),
+            ..Self::default()
+        }
+    }
+
+    /// Build a scanner configuration from a frontend request and the app settings
+    pub fn from_request(request: &ScanRequest, app_config: &VeloxConfig) -> VeloxResult<Self> {
+        let metadata = request.metadata.unwrap_or_default();
+        check_metadata_level(request, metadata)?;
+        let name_regex = request
+            .name_regex
+            .clone()
+            .filter(|pattern| !pattern.is_empty());
+        // Reject bad syntax now rather than after the scan has started
+        build_regex(name_regex.as_deref())?;
+
+        Ok(Self {
+            max_depth: request.max_depth.unwrap_or(100),
+            include_hidden: request.include_hidden,
+            follow_symlinks: request.follow_symlinks,
+            dedupe_symlink_targets: request.dedupe_symlink_targets.unwrap_or(false),
+            progress_interval_ms: app_config.progress_emit_interval_ms,
+            progress_channel_capacity: DEFAULT_PROGRESS_CAPACITY,
+            include_globs: request.include_globs.clone().unwrap_or_default(),
+            exclude_globs: request.exclude_globs.clone().unwrap_or_default(),
+            estimate_total: request.estimate_total.unwrap_or(false),
+            stream_batch_size: request.stream_batch_size.filter(|size| *size > 0),
+            max_entries: request.max_entries.filter(|max| *max > 0),
+            entry_filter: request.entry_filter.unwrap_or_default(),
+            metadata,
+            timestamp_format: request.timestamp_format.unwrap_or_default(),
+            byte_unit: app_config.byte_unit,
+            read_permissions: request.read_permissions.unwrap_or(false),
+            detect_sparse: request.detect_sparse.unwrap_or(false),
+            report_broken_symlinks: request.report_broken_symlinks.unwrap_or(false),
+            only_empty_files: request.only_empty_files.unwrap_or(false),
+            aggregate_dir_sizes: request.aggregate_dir_sizes.unwrap_or(false),
+            hash_files: request.hash_files.unwrap_or(false),
+            hash_max_bytes: request.hash_max_bytes,
+            hash_algorithm: request.hash_algorithm.unwrap_or_default(),
+            io_retries: request.io_retries.unwrap_or(2),
+            dedup_memory_mode: request.dedup_memory_mode.unwrap_or_default(),
+            max_bytes_per_sec: request.max_bytes_per_sec.filter(|rate| *rate > 0),
+            detect_mime: request.detect_mime.unwrap_or(false),
+            parallel: request.parallel.unwrap_or(false),
+            threads: request.threads.filter(|n| *n > 0).unwrap_or_else(num_cpus::get),
+            concurrent_roots: request.concurrent_roots.unwrap_or(false),
+            min_size: request.min_size,
+            max_size: request.max_size,
+            count_filtered: request.count_filtered.unwrap_or(false),
+            modified_after: parse_timestamp("modifiedAfter", request.modified_after.as_deref())?,
+            modified_before: parse_timestamp("modifiedBefore", request.modified_before.as_deref())?,
+            include_extensions: request.include_extensions.as_deref().map(normalize_extensions),
+            exclude_extensions: request.exclude_extensions.as_deref().map(normalize_extensions),
+            name_regex,
+            name_contains: request.name_contains.clone().filter(|needle| !needle.is_empty()),
+            filter_directory_names: request.filter_directory_names.unwrap_or(false),
+            skip_system_dirs: request.skip_system_dirs.unwrap_or(false),
+            skip_dir_names: request.skip_dir_names.clone().unwrap_or_default(),
+            yield_interval: DEFAULT_YIELD_INTERVAL,
+            largest_files: None,
+            newest_files: None,
+            group_by_extension: false,
+            group_by_age: false,
+            group_by_category: false,
+            totals_only: false,
+            respect_gitignore: request.respect_gitignore.unwrap_or(false),
+            roots: request.root_paths(),
+            checkpoint_path: request.checkpoint_path.clone(),
+            checkpoint: request
+                .checkpoint_path
+                .as_ref()
+                .map(|_| ScanCheckpoint::new(request.clone())),
+            memory_limit_bytes: Some(app_config.memory_limit_bytes).filter(|limit| *limit > 0),
+            subtree: None,
+            generate_ids: request.generate_ids.unwrap_or(false),
+            priority: request.priority.unwrap_or_default(),
+        })
+    }
+
+    /// Lowercase directory names pruned by `skip_system_dirs`, if it's set
+    fn skipped_dir_names(&self) -> Option<HashSet<String>> {
+        if !self.skip_system_dirs {
+            return None;
+        }
+
+        let names = if self.skip_dir_names.is_empty() {
+            SYSTEM_DIR_NAMES.iter().map(|name| name.to_lowercase()).collect()
+        } else {
+            self.skip_dir_names.iter().map(|name| name.to_lowercase()).collect()
+        };
+        Some(names)
+    }
+}
+
+/// Reject filters that need metadata the requested level won't read
+pub fn check_metadata_level(request: &ScanRequest, level: MetadataLevel) -> VeloxResult<()> {
+    let time_filtered = request.modified_after.is_some() || request.modified_before.is_some();
+    let size_filtered = request.min_size.is_some() || request.max_size.is_some();
+
+    if time_filtered && level != MetadataLevel::Full {
+        return Err(VeloxError::InvalidArgument(
+            "modifiedAfter/modifiedBefore need metadata level full".to_string(),
+        ));
+    }
+    if size_filtered && level == MetadataLevel::None {
+        return Err(VeloxError::InvalidArgument(
+            "minSize/maxSize need metadata level basic or full".to_string(),
+        ));
+    }
+    if request.only_empty_files.unwrap_or(false) && level == MetadataLevel::None {
+        return Err(VeloxError::InvalidArgument(
+            "onlyEmptyFiles needs metadata level basic or full".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parse an optional RFC3339 timestamp from a request field
+pub fn parse_timestamp(field: &str, value: Option<&str>) -> VeloxResult<Option<DateTime<Utc>>> {
+    value
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(raw)
+                .map(|t| t.with_timezone(&Utc))
+                .map_err(|e| VeloxError::InvalidArgument(format!("{}: {} ({})", field, raw, e)))
+        })
+        .transpose()
+}
+
+/// Compile a list of glob patterns into a single matcher.
+/// Returns `None` for an empty list so callers can skip matching entirely.
+pub fn build_globset(patterns: &[String]) -> VeloxResult<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| VeloxError::InvalidPattern(format!("{}: {}", pattern, e)))?;
+        builder.add(glob);
+    }
+
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| VeloxError::InvalidPattern(e.to_string()))
+}
+
+/// Compile an optional name pattern, rejecting invalid syntax up front
+pub fn build_regex(pattern: Option<&str>) -> VeloxResult<Option<Regex>> {
+    pattern
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|e| VeloxError::InvalidPattern(format!("{}: {}", pattern, e)))
+        })
+        .transpose()
+}
+
+/// Check that a scan root exists and is a directory
+pub fn validate_root(root_path: &str) -> VeloxResult<()> {
+    let metadata = std::fs::metadata(root_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => VeloxError::InvalidPath(root_path.to_string()),
+        std::io::ErrorKind::PermissionDenied => VeloxError::AccessDenied(root_path.to_string()),
+        _ => VeloxError::io_at(root_path, e),
+    })?;
+
+    if !metadata.is_dir() {
+        return Err(VeloxError::InvalidPath(format!(
+            "{} is not a directory",
+            root_path
+        )));
+    }
+
+    Ok(())
+}
+
+/// Find the root among `roots` that contains `subtree`, returning it along
+/// with `subtree` spelled under that root, as the walk would report it
+pub fn locate_subtree(roots: &[String], subtree: &str) -> Option<(String, PathBuf)> {
+    let target = std::fs::canonicalize(subtree).ok()?;
+    roots.iter().find_map(|root| {
+        let base = std::fs::canonicalize(root).ok()?;
+        let relative = target.strip_prefix(&base).ok()?;
+        let path = if relative.as_os_str().is_empty() {
+            PathBuf::from(root)
+        } else {
+            Path::new(root).join(relative)
+        };
+        Some((root.clone(), path))
+    })
+}
+
+/// Sample this process's resident memory every `MEMORY_CHECK_INTERVAL` while
+/// a scan runs. Past `limit` bytes it reports the overrun as a progress
+/// update, cancels the session and returns the error the scan should end with.
+async fn guard_memory(
+    limit: u64,
+    session: Arc<ScanSession>,
+    tx: Arc<ProgressQueue>,
+    scan_id: String,
+    start_time: Instant,
+    byte_unit: ByteUnit,
+) -> Option<VeloxError> {
+    let pid = sysinfo::get_current_pid().ok()?;
+    let mut system = System::new();
+    let mut interval = tokio::time::interval(MEMORY_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        if session.is_cancelled() {
+            return None;
+        }
+
+        system.refresh_process(pid);
+        let Some(used) = system.process(pid).map(|process| process.memory()) else {
+            continue;
+        };
+        if used <= limit {
+            continue;
+        }
+
+        tracing::warn!(
+            "🧠 Scan {} stopped: {} in use, limit {}",
+            scan_id,
+            format_bytes(used, byte_unit),
+            format_bytes(limit, byte_unit)
+        );
+
+        let (files, directories, bytes) = session.totals();
+        tx.send(ScanProgress {
+            scan_id,
+            seq: 0,
+            current_path: String::new(),
+            root: None,
+            files_scanned: files,
+            directories_scanned: directories,
+            bytes_scanned: bytes,
+            bytes_scanned_formatted: format_bytes(bytes, byte_unit),
+            progress_percent: 0.0,
+            estimated_total: None,
+            files_per_sec: 0.0,
+            eta_ms: None,
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+            status: ScanStatus::MemoryLimitExceeded,
+        });
+        session.cancel();
+
+        return Some(VeloxError::MemoryLimitExceeded { used, limit });
+    }
+}
+
+/// Roll file sizes up into their ancestor directories and count direct children.
+/// Directory entries get their `size` replaced by the recursive byte total.
+fn aggregate_directory_sizes(entries: &mut [FileEntry], byte_unit: ByteUnit) {
+    // (direct children, recursive bytes) keyed by directory path
+    let mut dir_stats: HashMap<PathBuf, (u64, u64)> = entries
+        .iter()
+        .filter(|e| e.is_directory)
+        .map(|e| (PathBuf::from(&e.path), (0, 0)))
+        .collect();
+
+    for entry in entries.iter() {
+        let path = Path::new(&entry.path);
+
+        if let Some(stats) = path.parent().and_then(|p| dir_stats.get_mut(p)) {
+            stats.0 += 1;
+        }
+
+        if entry.is_file {
+            for ancestor in path.ancestors().skip(1) {
+                match dir_stats.get_mut(ancestor) {
+                    Some(stats) => stats.1 += entry.size,
+                    None => break,
+                }
+            }
+        }
+    }
+
+    for entry in entries.iter_mut().filter(|e| e.is_directory) {
+        if let Some((children, bytes)) = dir_stats.get(Path::new(&entry.path)) {
+            entry.children_count = Some(*children);
+            entry.size = *bytes;
+            entry.size_formatted = format_bytes(*bytes, byte_unit);
+        }
+    }
+}
+
+/// Percentage of `done` against an optional estimate, clamped to 100
+fn percent_of(done: u64, estimate: Option<u64>) -> f64 {
+    match estimate {
+        Some(total) if total > 0 => ((done as f64 / total as f64) * 100.0).min(100.0),
+        _ => 0.0,
+    }
+}
+
+/// Window over which throughput is measured
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Rolling entries-per-second rate over the last `THROUGHPUT_WINDOW`.
+/// Only sampled on throttled progress emits, so the deque stays tiny.
+struct ThroughputTracker {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl ThroughputTracker {
+    fn new() -> Self {
+        Self {
+            samples: VecDeque::from([(Instant::now(), 0)]),
+        }
+    }
+
+    /// Record the running total and return the current rate
+    fn record(&mut self, processed: u64) -> f64 {
+        let now = Instant::now();
+        self.samples.push_back((now, processed));
+
+        // Keep one sample at or before the window start as the baseline
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= THROUGHPUT_WINDOW {
+            self.samples.pop_front();
+        }
+
+        let (since, base) = self.samples[0];
+        let elapsed = now.duration_since(since).as_secs_f64();
+        if elapsed > 0.0 {
+            processed.saturating_sub(base) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Throttle for periodic progress updates, measuring throughput across them
+struct ProgressTicker {
+    interval: Duration,
+    last: Instant,
+    throughput: ThroughputTracker,
+}
+
+impl ProgressTicker {
+    fn new(interval_ms: u64) -> Self {
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            last: Instant::now(),
+            throughput: ThroughputTracker::new(),
+        }
+    }
+
+    /// True at most once per interval; restarts the interval when it fires
+    fn tick(&mut self) -> bool {
+        if self.last.elapsed() < self.interval {
+            return false;
+        }
+        self.last = Instant::now();
+        true
+    }
+}
+
+/// Remaining time at the current rate; only known when a total was estimated
+fn eta_ms(done: u64, estimate: Option<u64>, per_sec: f64) -> Option<u64> {
+    let total = estimate?;
+    if per_sec <= 0.0 {
+        return None;
+    }
+    Some((total.saturating_sub(done) as f64 / per_sec * 1000.0) as u64)
+}
+
+/// Progress queue between the walker and the emitter task. Periodic updates
+/// are lossy: pushing never waits, and when the queue is full the oldest
+/// periodic update is dropped. Status changes (pause, cancel, completion) are
+/// always kept, so the final update is delivered reliably.
+struct ProgressQueue {
+    /// Queued updates, each flagged with whether it may be dropped
+    items: Mutex<VecDeque<(bool, ScanProgress)>>,
+    capacity: usize,
+    closed: AtomicBool,
+    notify: Notify,
+}
+
+impl ProgressQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            items: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            closed: AtomicBool::new(false),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Queue a periodic update, evicting the oldest periodic one when full
+    fn send_lossy(&self, progress: ScanProgress) {
+        let mut items = self.items.lock();
+        if items.iter().filter(|(lossy, _)| *lossy).count() >= self.capacity {
+            if let Some(oldest) = items.iter().position(|(lossy, _)| *lossy) {
+                items.remove(oldest);
+            }
+        }
+        items.push_back((true, progress));
+        drop(items);
+        self.notify.notify_one();
+    }
+
+    /// Queue a status change; these are never dropped
+    fn send(&self, progress: ScanProgress) {
+        self.items.lock().push_back((false, progress));
+        self.notify.notify_one();
+    }
+
+    /// No more updates will be queued; the receiver drains what is left
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.notify.notify_one();
+    }
+
+    /// Next queued update, or `None` once closed and drained
+    async fn recv(&self) -> Option<ScanProgress> {
+        loop {
+            if let Some((_, progress)) = self.items.lock().pop_front() {
+                return Some(progress);
+            }
+            if self.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+/// Spawned task that throttles and numbers progress updates for one session,
+/// fed through a lossy `ProgressQueue` so senders never block on the sink.
+/// Numbering continues from the session's last `seq`, so a follow-up pass
+/// such as image hashing picks up where the scan's updates left off.
+pub struct ProgressEmitter {
+    queue: Arc<ProgressQueue>,
+    handle: JoinHandle<()>,
+}
+
+impl ProgressEmitter {
+    pub fn spawn<S: ProgressSink>(
+        sink: S,
+        session: &Arc<ScanSession>,
+        interval_ms: u64,
+        capacity: usize,
+    ) -> Self {
+        let queue = Arc::new(ProgressQueue::new(capacity));
+        let rx = queue.clone();
+        let seq = session.progress_seq.clone();
+        let scan_id = session.id.to_string();
+
+        let handle = tokio::spawn(async move {
+            let started = Instant::now();
+            let mut last_emit: HashMap<Option<String>, Instant> = HashMap::new();
+            while let Some(mut progress) = rx.recv().await {
+                // Throttle emissions to prevent UI flooding; status changes always go through.
+                // Each concurrent root is throttled on its own so every bar keeps moving.
+                let since = last_emit.get(&progress.root).copied().unwrap_or(started);
+                if since.elapsed().as_millis() >= interval_ms as u128
+                    || progress.status != ScanStatus::Scanning
+                {
+                    // Numbered here so throttled drops never show up as gaps
+                    progress.seq = seq.fetch_add(1, Ordering::Relaxed) + 1;
+                    sink.emit_progress(&progress);
+                    last_emit.insert(progress.root.clone(), Instant::now());
+                }
+            }
+            tracing::debug!("Progress emitter completed for scan: {}", scan_id);
+        });
+
+        Self { queue, handle }
+    }
+
+    /// Queue a periodic update; it may be dropped when the emitter falls behind
+    pub fn send(&self, progress: ScanProgress) {
+        self.queue.send_lossy(progress);
+    }
+
+    /// Close the queue and wait until every kept update has been emitted
+    pub async fn finish(self) {
+        self.queue.close();
+        self.handle.await.ok();
+    }
+}
+
+/// Checkpoint bookkeeping for one scan. Writes are best effort: a failed
+/// write is logged and the scan carries on.
+struct CheckpointTracker {
+    path: PathBuf,
+    checkpoint: ScanCheckpoint,
+    /// Top-level entry of the current root that is still being walked
+    open_subtree: Option<String>,
+    last_saved: Instant,
+}
+
+impl CheckpointTracker {
+    fn new(path: &str, checkpoint: ScanCheckpoint) -> Self {
+        Self {
+            path: PathBuf::from(path),
+            checkpoint,
+            open_subtree: None,
+            last_saved: Instant::now(),
+        }
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = self.checkpoint.save(&self.path) {
+            tracing::warn!("⚠️ Failed to write checkpoint: {}", e);
+        }
+        self.last_saved = Instant::now();
+    }
+
+    /// A new top-level entry means the previous one's subtree is finished.
+    /// `totals` must not include the new entry yet.
+    fn enter_subtree(&mut self, path: &Path, totals: (u64, u64, u64)) {
+        let entered = path.to_string_lossy().to_string();
+        if let Some(done) = self.open_subtree.replace(entered) {
+            self.checkpoint.complete_subtree(done, totals);
+            if self.last_saved.elapsed() >= CHECKPOINT_INTERVAL {
+                self.save();
+            }
+        }
+    }
+
+    fn finish_root(&mut self, summary: RootSummary, totals: (u64, u64, u64)) {
+        self.open_subtree = None;
+        self.checkpoint.complete_root(summary, totals);
+        self.save();
+    }
+
+    /// The scan finished, so there is nothing left to resume
+    fn discard(self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("⚠️ Failed to remove checkpoint {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+/// Token bucket pacing reads to a byte rate, with up to one second of burst
+struct ByteRateLimiter {
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl ByteRateLimiter {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            rate: bytes_per_sec as f64,
+            tokens: bytes_per_sec as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Charge `bytes` and sleep off any overdraft. Going into debt lets a file
+    /// larger than the bucket through instead of waiting forever, and the wait
+    /// is sliced so a cancelled scan stops sleeping promptly.
+    async fn acquire(&mut self, bytes: u64, session: &ScanSession) {
+        let now = Instant::now();
+        let refill = now.duration_since(self.last_refill).as_secs_f64() * self.rate;
+        self.tokens = (self.tokens + refill).min(self.rate) - bytes as f64;
+        self.last_refill = now;
+
+        if self.tokens >= 0.0 {
+            return;
+        }
+
+        let mut wait = Duration::from_secs_f64(-self.tokens / self.rate);
+        let slice = Duration::from_millis(PAUSE_POLL_INTERVAL_MS);
+        while !wait.is_zero() && !session.is_cancelled() {
+            let step = wait.min(slice);
+            tokio::time::sleep(step).await;
+            wait -= step;
         }
     }
 }
 
-/// High-performance directory scanner
-pub struct DirectoryScanner {
+/// High-performance directory scanner, reporting through any `ProgressSink`
+#[derive(Clone)]
+pub struct DirectoryScanner<S: ProgressSink = Window> {
     config: ScanConfig,
     session: Arc<ScanSession>,
-    window: Window,
+    sink: S,
+    /// Entries collected so far, counted against `max_entries` by every root
+    collected: Arc<AtomicUsize>,
+    /// Index of the next streamed batch, unique across concurrent roots
+    batch_index: Arc<AtomicU64>,
+    /// Root stamped on progress updates when roots are walked concurrently
+    progress_root: Option<String>,
 }
 
-impl DirectoryScanner {
-    pub fn new(session: Arc<ScanSession>, window: Window, config: ScanConfig) -> Self {
+impl<S: ProgressSink> DirectoryScanner<S> {
+    pub fn new(session: Arc<ScanSession>, sink: S, config: ScanConfig) -> Self {
         Self {
             config,
             session,
-            window,
+            sink,
+            collected: Arc::new(AtomicUsize::new(0)),
+            batch_index: Arc::new(AtomicU64::new(0)),
+            progress_root: None,
+        }
+    }
+
+    /// Scanner for one root of a concurrent scan. It reports into a per-root
+    /// session and shares the sink, entry cap and batch numbering with `self`.
+    fn for_root(&self, root: &str) -> Self {
+        Self {
+            config: self.config.clone(),
+            session: Arc::new(ScanSession::for_root(&self.session, root.to_string())),
+            sink: self.sink.clone(),
+            collected: self.collected.clone(),
+            batch_index: self.batch_index.clone(),
+            progress_root: Some(root.to_string()),
+        }
+    }
+
+    /// Roots to walk, falling back to the session root when none are configured
+    fn roots(&self) -> Vec<String> {
+        if self.config.roots.is_empty() {
+            vec![self.session.root_path.clone()]
+        } else {
+            self.config.roots.clone()
         }
     }
 
     /// Execute the scan with real-time progress streaming
     pub async fn scan(&self) -> VeloxResult<ScanResult> {
+        match self.config.priority {
+            ScanPriority::Normal => self.run().await,
+            ScanPriority::Low => self.run_low_priority().await,
+        }
+    }
+
+    /// Run the scan on a dedicated thread with its own runtime, lowered to
+    /// the minimum OS priority. Threads the walk starts there inherit it
+    /// where the OS passes priority on. The thread exits with the scan,
+    /// finished or cancelled, so no shared runtime thread is ever left
+    /// deprioritized and there is nothing to restore.
+    async fn run_low_priority(&self) -> VeloxResult<ScanResult> {
+        let scanner = self.clone();
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+
+        std::thread::Builder::new()
+            .name(format!("velox-scan-{}", self.session.id))
+            .spawn(move || {
+                if let Err(e) = set_current_thread_priority(ThreadPriority::Min) {
+                    tracing::warn!("⚠️ Could not lower scan thread priority: {:?}", e);
+                }
+
+                let result = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(VeloxError::from)
+                    .and_then(|runtime| runtime.block_on(scanner.run()));
+                done_tx.send(result).ok();
+            })?;
+
+        done_rx
+            .await
+            .unwrap_or_else(|_| Err(VeloxError::Unknown("Low-priority scan thread panicked".into())))
+    }
+
+    /// The scan itself, on the calling task
+    async fn run(&self) -> VeloxResult<ScanResult> {
         let start_time = Instant::now();
-        let root_path = &self.session.root_path;
+        let roots = self.roots();
         let scan_id = self.session.id.to_string();
 
-        tracing::info!("🔍 Starting scan: {} for path: {}", scan_id, root_path);
+        tracing::info!("🔍 Starting scan: {} for path: {}", scan_id, roots.join(", "));
 
-        // Validate path
-        let path = Path::new(root_path);
-        if !path.exists() {
-            return Err(VeloxError::InvalidPath(root_path.clone()));
+        // Validate every root before walking any of them
+        for root_path in &roots {
+            validate_root(root_path)?;
         }
 
-        if !path.is_dir() {
-            return Err(VeloxError::InvalidPath(format!(
-                "{} is not a directory",
-                root_path
-            )));
-        }
+        tracing::debug!(
+            "Progress emit interval: {}ms for scan: {}",
+            self.config.progress_interval_ms,
+            scan_id
+        );
 
-        // Channel for progress updates
-        let (tx, mut rx) = mpsc::channel::<ScanProgress>(100);
-        let window_clone = self.window.clone();
-        let scan_id_clone = scan_id.clone();
+        // Lossy queue for progress updates so the walker never blocks on the emitter
+        let emitter = ProgressEmitter::spawn(
+            self.sink.clone(),
+            &self.session,
+            self.config.progress_interval_ms,
+            self.config.progress_channel_capacity,
+        );
+        let tx = emitter.queue.clone();
 
-        // Spawn progress emitter task
-        let progress_handle = tokio::spawn(async move {
-            let mut last_emit = Instant::now();
-            while let Some(progress) = rx.recv().await {
-                // Throttle emissions to prevent UI flooding
-                if last_emit.elapsed().as_millis() >= 50 || progress.status != ScanStatus::Scanning {
-                    window_clone
-                        .emit("velox:scan:progress", &progress)
-                        .ok();
-                    last_emit = Instant::now();
-                }
-            }
-            tracing::debug!("Progress emitter completed for scan: {}", scan_id_clone);
+        // Watch memory alongside the walk; over the limit it cancels the scan
+        let memory_guard = self.config.memory_limit_bytes.map(|limit| {
+            tokio::spawn(guard_memory(
+                limit,
+                self.session.clone(),
+                tx.clone(),
+                scan_id.clone(),
+                start_time,
+                self.config.byte_unit,
+            ))
         });
 
         // Perform the actual scan
-        let result = self.execute_scan(&scan_id, root_path, tx, start_time).await;
+        let mut result = if self.walks_roots_concurrently(&roots) {
+            self.scan_roots_concurrently(&scan_id, &roots, &tx, start_time).await
+        } else {
+            self.execute_scan(&scan_id, &roots, &tx, start_time).await
+        };
 
+        // Report why the scan stopped when the guard was the one that cancelled it
+        if let Some(guard) = memory_guard {
+            guard.abort();
+            if let Ok(Some(exceeded)) = guard.await {
+                if matches!(result, Err(VeloxError::ScanCancelled)) {
+                    result = Err(exceeded);
+                }
+            }
+        }
         // Wait for progress emitter to finish
-        progress_handle.await.ok();
+        emitter.finish().await;
 
         // Emit final result
         match &result {
             Ok(scan_result) => {
-                self.window
-                    .emit("velox:scan:complete", scan_result)
-                    .ok();
+                self.sink.emit_complete(scan_result);
                 tracing::info!(
                     "✅ Scan complete: {} files, {} dirs, {} in {}ms",
                     scan_result.total_files,
@@ -112,12 +1026,7 @@ impl DirectoryScanner {
                 );
             }
             Err(e) => {
-                self.window
-                    .emit("velox:scan:error", serde_json::json!({
-                        "scanId": scan_id,
-                        "error": e.to_string()
-                    }))
-                    .ok();
+                self.sink.emit_error(&scan_id, e);
                 tracing::error!("❌ Scan failed: {}", e);
             }
         }
@@ -125,157 +1034,1175 @@ impl DirectoryScanner {
         result
     }
 
-    async fn execute_scan(
+    /// Whether a file of `size` bytes falls inside the configured size range
+    fn within_size_range(&self, size: u64) -> bool {
+        self.config.min_size.map_or(true, |min| size >= min)
+            && self.config.max_size.map_or(true, |max| size <= max)
+    }
+
+    /// Whether a modification time falls inside the configured window.
+    /// Files without a known timestamp never match an active window.
+    fn within_time_window(&self, modified: Option<DateTime<Utc>>) -> bool {
+        if self.config.modified_after.is_none() && self.config.modified_before.is_none() {
+            return true;
+        }
+
+        match modified {
+            Some(t) => {
+                self.config.modified_after.map_or(true, |after| t >= after)
+                    && self.config.modified_before.map_or(true, |before| t <= before)
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a file of `size` bytes is small enough to hash
+    fn within_hash_limit(&self, size: u64) -> bool {
+        self.config.hash_max_bytes.map_or(true, |max| size <= max)
+    }
+
+    /// Build the `FileEntry` for a walked item in the configured formats
+    fn file_entry(&self, entry: &WalkedEntry) -> FileEntry {
+        let mut file_entry = entry.to_file_entry(self.config.timestamp_format, self.config.byte_unit);
+        if self.config.generate_ids {
+            file_entry.id = NEXT_ENTRY_ID.fetch_add(1, Ordering::Relaxed).to_string();
+        }
+        file_entry
+    }
+
+    /// Send a throttled `Scanning` update for a walked entry
+    fn report_walked(
         &self,
         scan_id: &str,
-        root_path: &str,
-        tx: mpsc::Sender<ScanProgress>,
+        path: &Path,
+        ticker: &mut ProgressTicker,
+        tx: &ProgressQueue,
+        estimated_total: Option<u64>,
+        start_time: Instant,
+    ) {
+        if !ticker.tick() {
+            return;
+        }
+
+        let (total_files, total_directories, total_size) = self.session.totals();
+        let processed = total_files + total_directories;
+        let files_per_sec = ticker.throughput.record(processed);
+
+        tx.send_lossy(ScanProgress {
+            scan_id: scan_id.to_string(),
+            seq: 0,
+            current_path: path.to_string_lossy().to_string(),
+            root: self.progress_root.clone(),
+            files_scanned: total_files,
+            directories_scanned: total_directories,
+            bytes_scanned: total_size,
+            bytes_scanned_formatted: format_bytes(total_size, self.config.byte_unit),
+            // Stays at 0.0 unless a counting pass provided a total
+            progress_percent: percent_of(processed, estimated_total),
+            estimated_total,
+            files_per_sec,
+            eta_ms: eta_ms(processed, estimated_total, files_per_sec),
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+            status: ScanStatus::Scanning,
+        });
+    }
+
+    /// Announce a phase transition so the UI can label what the scan is doing
+    fn enter_phase(&self, scan_id: &str, phase: ScanPhase, detail: Option<String>) {
+        tracing::debug!("Scan {} entering {:?} phase {:?}", scan_id, phase, detail);
+        self.sink.emit_phase(&ScanPhaseEvent {
+            scan_id: scan_id.to_string(),
+            phase,
+            detail,
+        });
+    }
+
+    /// Emit the pending batch of entries to the frontend, if any
+    fn flush_batch(&self, scan_id: &str, batch: &mut Vec<FileEntry>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let payload = ScanBatch {
+            scan_id: scan_id.to_string(),
+            batch_index: self.batch_index.fetch_add(1, Ordering::Relaxed),
+            entries: std::mem::take(batch),
+        };
+        self.sink.emit_batch(&payload);
+    }
+
+    /// Let other tasks run every `yield_interval` walked items. Walking is
+    /// otherwise synchronous and would hold the worker thread until a send blocks.
+    /// Also keeps the session's activity timestamp fresh for the stale-scan sweep.
+    async fn yield_periodically(&self, walked: u64) {
+        if walked % ACTIVITY_INTERVAL == 0 {
+            self.session.touch();
+        }
+
+        let interval = match self.config.priority {
+            ScanPriority::Normal => self.config.yield_interval,
+            ScanPriority::Low => self.config.yield_interval.clamp(1, LOW_PRIORITY_YIELD_INTERVAL),
+        } as u64;
+        if interval > 0 && walked % interval == 0 {
+            if self.config.priority == ScanPriority::Low {
+                // Also offer the OS thread to other processes' work
+                std::thread::yield_now();
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    /// Build the filtered walker shared by the counting and scanning passes
+    fn walk(&self, filter: &WalkFilter, with_metadata: bool) -> WalkIter {
+        let options = WalkOptions {
+            max_depth: self.config.max_depth,
+            follow_symlinks: self.config.follow_symlinks,
+            with_metadata,
+        };
+
+        if self.config.respect_gitignore {
+            walk_gitignore(filter.clone(), options)
+        } else if self.config.parallel {
+            walk_parallel(filter.clone(), options, self.config.threads, self.session.clone())
+        } else {
+            walk_sequential(filter.clone(), options)
+        }
+    }
+
+    /// Whether `roots` can be walked at the same time. Ranking, grouping,
+    /// checkpoints, symlink dedupe and rate caps keep state across roots.
+    fn walks_roots_concurrently(&self, roots: &[String]) -> bool {
+        let config = &self.config;
+        config.concurrent_roots
+            && roots.len() > 1
+            && config.largest_files.is_none()
+            && config.newest_files.is_none()
+            && !config.group_by_extension
+            && !config.group_by_age
+            && !config.group_by_category
+            && config.checkpoint_path.is_none()
+            && !(config.follow_symlinks && config.dedupe_symlink_targets)
+            && config.max_bytes_per_sec.is_none()
+    }
+
+    /// Walk every root on its own task, at most `threads` at a time, merging
+    /// results as they complete. Progress from each root carries its path.
+    /// A cancel reaches every root through the shared flags; any other
+    /// failure aborts the roots still running.
+    async fn scan_roots_concurrently(
+        &self,
+        scan_id: &str,
+        roots: &[String],
+        tx: &Arc<ProgressQueue>,
         start_time: Instant,
     ) -> VeloxResult<ScanResult> {
+        tracing::info!("🔀 Walking {} roots concurrently: {}", roots.len(), scan_id);
+
+        let permits = Arc::new(Semaphore::new(self.config.threads.max(1)));
+        let mut tasks = JoinSet::new();
+        for (index, root) in roots.iter().enumerate() {
+            let scanner = self.for_root(root);
+            let permits = permits.clone();
+            let tx = tx.clone();
+            let scan_id = scan_id.to_string();
+            let root = root.clone();
+            tasks.spawn(async move {
+                // The semaphore is never closed, so acquiring only waits
+                let _permit = permits.acquire_owned().await.ok();
+                let result = scanner.execute_scan(&scan_id, &[root], &tx, start_time).await;
+                (index, result)
+            });
+        }
+
         let mut entries: Vec<FileEntry> = Vec::new();
-        let mut total_files: u64 = 0;
-        let mut total_directories: u64 = 0;
-        let mut total_size: u64 = 0;
+        let mut errors: Vec<ScanErrorEntry> = Vec::new();
+        let mut root_summaries: Vec<(usize, RootSummary)> = Vec::with_capacity(roots.len());
+        let mut depths = DepthHistogram::default();
+        let mut truncated = false;
+        let mut pruned_directories: u64 = 0;
+        let mut failure: Option<VeloxError> = None;
 
-        let walker = WalkDir::new(root_path)
-            .max_depth(self.config.max_depth)
-            .follow_links(self.config.follow_symlinks)
-            .into_iter()
-            .filter_entry(|e| {
-                if !self.config.include_hidden {
-                    !e.file_name()
-                        .to_str()
-                        .map(|s| s.starts_with('.'))
-                        .unwrap_or(false)
-                } else {
-                    true
+        while let Some(joined) = tasks.join_next().await {
+            let (index, result) = match joined {
+                Ok(done) => done,
+                // Only tasks aborted after an earlier failure end up here, or a panic
+                Err(e) if e.is_cancelled() => continue,
+                Err(e) => {
+                    tasks.abort_all();
+                    let error = VeloxError::Unknown(format!("Root scan task failed: {}", e));
+                    failure.get_or_insert(error);
+                    continue;
                 }
-            });
+            };
+
+            match result {
+                Ok(mut scan) => {
+                    entries.append(&mut scan.entries);
+                    errors.append(&mut scan.errors);
+                    root_summaries.extend(scan.roots.into_iter().map(|summary| (index, summary)));
+                    depths.add_buckets(&scan.depth_distribution);
+                    truncated |= scan.truncated;
+                    pruned_directories += scan.pruned_directories;
+                }
+                Err(e) => {
+                    // Cancelled roots stop on their own and still flush what they collected
+                    if !matches!(e, VeloxError::ScanCancelled) {
+                        tasks.abort_all();
+                    }
+                    failure.get_or_insert(e);
+                }
+            }
+        }
+
+        if let Some(e) = failure {
+            return Err(e);
+        }
+
+        // Roots that were still walking can overshoot the shared cap by an entry each
+        if let Some(max) = self.config.max_entries.filter(|max| entries.len() > *max) {
+            entries.truncate(max);
+            truncated = true;
+        }
+
+        // Report roots in request order, not completion order
+        root_summaries.sort_by_key(|(index, _)| *index);
+
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+        let status = if truncated {
+            ScanStatus::Truncated
+        } else {
+            ScanStatus::Completed
+        };
+
+        let (total_files, total_directories, total_size) = self.session.totals();
+        tx.send(ScanProgress {
+            scan_id: scan_id.to_string(),
+            seq: 0,
+            current_path: String::new(),
+            root: None,
+            files_scanned: total_files,
+            directories_scanned: total_directories,
+            bytes_scanned: total_size,
+            bytes_scanned_formatted: format_bytes(total_size, self.config.byte_unit),
+            progress_percent: 100.0,
+            estimated_total: Some(total_files + total_directories),
+            files_per_sec: 0.0,
+            eta_ms: None,
+            elapsed_ms: duration_ms,
+            status: status.clone(),
+        });
+
+        Ok(ScanResult {
+            scan_id: scan_id.to_string(),
+            root_path: roots.join(", "),
+            roots: root_summaries.into_iter().map(|(_, summary)| summary).collect(),
+            total_files,
+            total_directories,
+            total_size,
+            total_size_formatted: format_bytes(total_size, self.config.byte_unit),
+            entries,
+            errors,
+            extension_stats: None,
+            age_buckets: None,
+            category_stats: None,
+            depth_distribution: depths.into_buckets(),
+            duration_ms,
+            completed_at: Utc::now().to_rfc3339(),
+            truncated,
+            symlink_duplicates_skipped: 0,
+            pruned_directories,
+            hash_algorithm: self.config.hash_files.then_some(self.config.hash_algorithm),
+            status,
+            request: None,
+        })
+    }
 
-        let mut last_progress = Instant::now();
+    /// Fast counting pass used to estimate the total number of entries.
+    /// Only looks at the file type reported by the directory listing and
+    /// never reads metadata, so it stays cheap on huge trees.
+    async fn count_entries(
+        &self,
+        scan_id: &str,
+        filters: &[WalkFilter],
+        tx: &ProgressQueue,
+        start_time: Instant,
+    ) -> VeloxResult<u64> {
+        let mut counted: u64 = 0;
+        let mut walked: u64 = 0;
+        let mut ticker = ProgressTicker::new(self.config.progress_interval_ms);
+
+        for item in filters.iter().flat_map(|filter| self.walk(filter, false)) {
+            walked += 1;
+            self.yield_periodically(walked).await;
+
+            let WalkItem::Entry(entry) = item else {
+                continue;
+            };
 
-        for entry_result in walker {
-            // Check for cancellation
             if self.session.is_cancelled() {
-                tracing::info!("🛑 Scan cancelled: {}", scan_id);
-                
-                // Send cancellation progress
-                tx.send(ScanProgress {
-                    scan_id: scan_id.to_string(),
-                    current_path: String::new(),
-                    files_scanned: total_files,
-                    directories_scanned: total_directories,
-                    bytes_scanned: total_size,
-                    bytes_scanned_formatted: human_bytes(total_size as f64),
-                    progress_percent: 0.0,
-                    estimated_total: None,
-                    elapsed_ms: start_time.elapsed().as_millis() as u64,
-                    status: ScanStatus::Cancelled,
-                }).await.ok();
+                tracing::info!("🛑 Scan cancelled during counting: {}", scan_id);
+
+                tx.send(self.pre_pass_progress(
+                    scan_id,
+                    String::new(),
+                    None,
+                    ScanStatus::Cancelled,
+                    start_time,
+                ));
+
+                return Err(VeloxError::ScanCancelled);
+            }
+
+            if entry.file_type.is_dir() || entry.file_type.is_file() {
+                counted += 1;
+            }
+
+            if ticker.tick() {
+                tx.send_lossy(self.pre_pass_progress(
+                    scan_id,
+                    entry.path.to_string_lossy().to_string(),
+                    Some(counted),
+                    ScanStatus::Counting,
+                    start_time,
+                ));
+            }
+        }
+
+        tracing::debug!("Counted {} entries for scan: {}", counted, scan_id);
+        Ok(counted)
+    }
+
+    /// Size pass for `DedupMode::Approximate`. Walks every root once and
+    /// returns the sizes a bloom filter had already seen, so the real walk can
+    /// skip files whose size is certainly unique. Only non-empty regular files
+    /// count, as those are all `find_duplicates` compares.
+    async fn repeated_sizes(
+        &self,
+        scan_id: &str,
+        filters: &[WalkFilter],
+        expected_files: usize,
+        tx: &ProgressQueue,
+        start_time: Instant,
+    ) -> VeloxResult<HashSet<u64>> {
+        let mut seen: Bloom<u64> =
+            Bloom::new_for_fp_rate(expected_files.max(1), DEDUP_FALSE_POSITIVE_RATE);
+        let mut repeated: HashSet<u64> = HashSet::new();
+        let mut walked: u64 = 0;
+        let mut ticker = ProgressTicker::new(self.config.progress_interval_ms);
 
+        for item in filters.iter().flat_map(|filter| self.walk(filter, true)) {
+            walked += 1;
+            self.yield_periodically(walked).await;
+
+            let WalkItem::Entry(entry) = item else {
+                continue;
+            };
+
+            if self.session.is_cancelled() {
+                tracing::info!("🛑 Scan cancelled during size pass: {}", scan_id);
+                tx.send(self.pre_pass_progress(
+                    scan_id,
+                    String::new(),
+                    None,
+                    ScanStatus::Cancelled,
+                    start_time,
+                ));
                 return Err(VeloxError::ScanCancelled);
             }
 
-            match entry_result {
-                Ok(entry) => {
-                    let path = entry.path();
-                    let metadata = entry.metadata().ok();
+            let size = match &entry.metadata {
+                Some(Ok(metadata)) if entry.file_type.is_file() => metadata.len(),
+                _ => 0,
+            };
+            if size > 0 && seen.check_and_set(&size) {
+                repeated.insert(size);
+            }
+
+            if ticker.tick() {
+                tx.send_lossy(self.pre_pass_progress(
+                    scan_id,
+                    entry.path.to_string_lossy().to_string(),
+                    None,
+                    ScanStatus::Counting,
+                    start_time,
+                ));
+            }
+        }
+
+        tracing::debug!(
+            "Size pass found {} repeated sizes in {} entries for scan: {}",
+            repeated.len(),
+            walked,
+            scan_id
+        );
+        Ok(repeated)
+    }
+
+    /// Update sent by a pass that runs before anything is counted
+    fn pre_pass_progress(
+        &self,
+        scan_id: &str,
+        current_path: String,
+        estimated_total: Option<u64>,
+        status: ScanStatus,
+        start_time: Instant,
+    ) -> ScanProgress {
+        ScanProgress {
+            scan_id: scan_id.to_string(),
+            seq: 0,
+            current_path,
+            root: self.progress_root.clone(),
+            files_scanned: 0,
+            directories_scanned: 0,
+            bytes_scanned: 0,
+            bytes_scanned_formatted: format_bytes(0, self.config.byte_unit),
+            progress_percent: 0.0,
+            estimated_total,
+            files_per_sec: 0.0,
+            eta_ms: None,
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+            status,
+        }
+    }
+
+    async fn execute_scan(
+        &self,
+        scan_id: &str,
+        roots: &[String],
+        tx: &ProgressQueue,
+        start_time: Instant,
+    ) -> VeloxResult<ScanResult> {
+        let mut entries: Vec<FileEntry> = Vec::new();
+        let mut batch: Vec<FileEntry> = Vec::new();
+        let mut largest = self.config.largest_files.map(LargestFiles::new);
+        let mut newest = self.config.newest_files.map(NewestFiles::new);
+        let mut by_extension = self.config.group_by_extension.then(ExtensionTotals::default);
+        let mut by_age = self.config.group_by_age.then(AgeHistogram::new);
+        let mut depths = DepthHistogram::default();
+        let mut by_category = self.config.group_by_category.then(CategoryTotals::default);
+        let mut errors: Vec<ScanErrorEntry> = Vec::new();
+        let mut root_summaries: Vec<RootSummary> = Vec::with_capacity(roots.len());
+        let mut limiter = self.config.max_bytes_per_sec.map(ByteRateLimiter::new);
+        let mut seen_targets = (self.config.follow_symlinks && self.config.dedupe_symlink_targets)
+            .then(HashSet::new);
+        let mut symlink_duplicates_skipped: u64 = 0;
+        let mut truncated = false;
 
-                    let is_dir = entry.file_type().is_dir();
-                    let is_file = entry.file_type().is_file();
-                    let is_symlink = entry.file_type().is_symlink();
+        // Checkpoints only make sense when entries are collected or streamed
+        let collecting = largest.is_none()
+            && newest.is_none()
+            && by_extension.is_none()
+            && by_age.is_none()
+            && by_category.is_none()
+            && !self.config.totals_only;
+        let mut tracker = match (&self.config.checkpoint_path, &self.config.checkpoint) {
+            (Some(path), Some(checkpoint)) if collecting => {
+                Some(CheckpointTracker::new(path, checkpoint.clone()))
+            }
+            _ => None,
+        };
+        let mut resumed_root: Option<String> = None;
+        let mut resumed_subtrees: Option<Arc<HashSet<PathBuf>>> = None;
+        if let Some(tracker) = &tracker {
+            let checkpoint = &tracker.checkpoint;
+            self.session.restore_totals(
+                checkpoint.total_files,
+                checkpoint.total_directories,
+                checkpoint.total_size,
+            );
+            root_summaries.extend(checkpoint.completed_roots.iter().cloned());
+            resumed_root = checkpoint.current_root.clone();
+            resumed_subtrees = Some(Arc::new(
+                checkpoint.completed_subtrees.iter().map(PathBuf::from).collect(),
+            ));
 
-                    let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+            if !checkpoint.completed_roots.is_empty() || resumed_root.is_some() {
+                tracing::info!(
+                    "⏩ Resuming scan {}: {} roots and {} subtrees already done",
+                    scan_id,
+                    checkpoint.completed_roots.len(),
+                    checkpoint.completed_subtrees.len()
+                );
+            }
+        }
 
-                    if is_dir {
-                        total_directories += 1;
-                    } else if is_file {
-                        total_files += 1;
-                        total_size += size;
+        // Compile patterns once up front; invalid syntax fails the scan early
+        let include_set = build_globset(&self.config.include_globs)?;
+        let exclude_set = build_globset(&self.config.exclude_globs)?;
+        let name_regex = build_regex(self.config.name_regex.as_deref())?;
+        let name_contains = self.config.name_contains.as_deref().map(str::to_lowercase);
+        let skip_dir_names = self.config.skipped_dir_names().map(Arc::new);
+        let pruned_dirs = Arc::new(AtomicU64::new(0));
+        let filters: Vec<WalkFilter> = roots
+            .iter()
+            .filter(|root| !tracker.as_ref().is_some_and(|t| t.checkpoint.is_root_complete(root)))
+            .map(|root| WalkFilter {
+                root: PathBuf::from(root),
+                include_hidden: self.config.include_hidden,
+                include_set: include_set.clone(),
+                exclude_set: exclude_set.clone(),
+                include_extensions: self.config.include_extensions.clone(),
+                exclude_extensions: self.config.exclude_extensions.clone(),
+                name_regex: name_regex.clone(),
+                name_contains: name_contains.clone(),
+                filter_directory_names: self.config.filter_directory_names,
+                skip_subtrees: resumed_subtrees
+                    .clone()
+                    .filter(|_| resumed_root.as_deref() == Some(root.as_str())),
+                subtree: self.config.subtree.clone(),
+                skip_dir_names: skip_dir_names.clone(),
+                pruned_dirs: pruned_dirs.clone(),
+            })
+            .collect();
+
+        // Optional first pass so progress can be reported as a percentage
+        let estimated_total = if self.config.estimate_total {
+            self.enter_phase(scan_id, ScanPhase::Counting, None);
+            // Work restored from a checkpoint isn't walked again, so add it back
+            let (files, directories, _) = self.session.totals();
+            let counted = self.count_entries(scan_id, &filters, tx, start_time).await?;
+            // The walk below prunes the same directories again
+            pruned_dirs.store(0, Ordering::Relaxed);
+            Some(counted + files + directories)
+        } else {
+            None
+        };
+
+        // Approximate dedup learns which sizes repeat before collecting anything
+        let repeated_sizes = match self.config.dedup_memory_mode {
+            DedupMode::Exact => None,
+            DedupMode::Approximate => {
+                self.enter_phase(scan_id, ScanPhase::Counting, None);
+                let expected = estimated_total.map_or(DEDUP_BLOOM_CAPACITY, |total| total as usize);
+                let sizes = self.repeated_sizes(scan_id, &filters, expected, tx, start_time).await?;
+                pruned_dirs.store(0, Ordering::Relaxed);
+                Some(sizes)
+            }
+        };
+
+        let mut ticker = ProgressTicker::new(self.config.progress_interval_ms);
+        let mut walked: u64 = 0;
+
+        // Aggregation needs every collected entry, so it applies the entry filter itself
+        let filter_after_aggregation =
+            self.config.aggregate_dir_sizes && self.config.stream_batch_size.is_none();
+
+        // Roots are walked in order; a cancel returns early and skips the rest
+        for filter in &filters {
+            let root = filter.root.to_string_lossy().to_string();
+            let resuming_root = resumed_root.as_deref() == Some(root.as_str());
+            let before = match &mut tracker {
+                Some(tracker) if resuming_root => tracker.checkpoint.current_root_base,
+                Some(tracker) => {
+                    let totals = self.session.totals();
+                    tracker.checkpoint.begin_root(&root, totals);
+                    totals
+                }
+                None => self.session.totals(),
+            };
+            self.enter_phase(
+                scan_id,
+                ScanPhase::Walking,
+                Some(filter.root.to_string_lossy().to_string()),
+            );
+
+            let with_metadata = self.config.metadata != MetadataLevel::None;
+            for item in self.walk(filter, with_metadata) {
+                walked += 1;
+                self.yield_periodically(walked).await;
+
+                // Park while paused; a paused scan can still be cancelled
+                if self.session.is_paused() {
+                    tracing::info!("⏸️ Scan paused: {}", scan_id);
+
+                    let (total_files, total_directories, total_size) = self.session.totals();
+                    tx.send(ScanProgress {
+                        scan_id: scan_id.to_string(),
+                        seq: 0,
+                        current_path: String::new(),
+                        root: self.progress_root.clone(),
+                        files_scanned: total_files,
+                        directories_scanned: total_directories,
+                        bytes_scanned: total_size,
+                        bytes_scanned_formatted: format_bytes(total_size, self.config.byte_unit),
+                        progress_percent: percent_of(total_files + total_directories, estimated_total),
+                        estimated_total,
+                        files_per_sec: 0.0,
+                        eta_ms: None,
+                        elapsed_ms: start_time.elapsed().as_millis() as u64,
+                        status: ScanStatus::Paused,
+                    });
+
+                    while self.session.is_paused() && !self.session.is_cancelled() {
+                        tokio::time::sleep(Duration::from_millis(PAUSE_POLL_INTERVAL_MS)).await;
                     }
 
-                    // Create file entry
-                    let file_entry = FileEntry {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        name: entry.file_name().to_string_lossy().to_string(),
-                        path: path.to_string_lossy().to_string(),
-                        size,
-                        size_formatted: human_bytes(size as f64),
-                        is_directory: is_dir,
-                        is_file,
-                        is_symlink,
-                        extension: path
-                            .extension()
-                            .map(|e| e.to_string_lossy().to_string()),
-                        modified: metadata.as_ref().and_then(|m| {
-                            m.modified().ok().map(|t| {
-                                chrono::DateTime::<Utc>::from(t).to_rfc3339()
-                            })
-                        }),
-                        created: metadata.as_ref().and_then(|m| {
-                            m.created().ok().map(|t| {
-                                chrono::DateTime::<Utc>::from(t).to_rfc3339()
-                            })
-                        }),
-                        depth: entry.depth(),
-                        children_count: None,
-                    };
-
-                    entries.push(file_entry);
-
-                    // Send progress update (throttled)
-                    if last_progress.elapsed().as_millis() >= self.config.progress_interval_ms as u128 {
-                        tx.send(ScanProgress {
+                    if !self.session.is_cancelled() {
+                        tracing::info!("▶️ Scan resumed: {}", scan_id);
+
+                        let (total_files, total_directories, total_size) = self.session.totals();
+                        tx.send_lossy(ScanProgress {
                             scan_id: scan_id.to_string(),
-                            current_path: path.to_string_lossy().to_string(),
+                            seq: 0,
+                            current_path: String::new(),
+                            root: self.progress_root.clone(),
                             files_scanned: total_files,
                             directories_scanned: total_directories,
                             bytes_scanned: total_size,
-                            bytes_scanned_formatted: human_bytes(total_size as f64),
-                            progress_percent: 0.0, // Unknown total, so percentage not applicable
-                            estimated_total: None,
+                            bytes_scanned_formatted: format_bytes(total_size, self.config.byte_unit),
+                            progress_percent: percent_of(total_files + total_directories, estimated_total),
+                            estimated_total,
+                            files_per_sec: 0.0,
+                            eta_ms: None,
                             elapsed_ms: start_time.elapsed().as_millis() as u64,
                             status: ScanStatus::Scanning,
-                        }).await.ok();
-                        
-                        last_progress = Instant::now();
+                        });
                     }
                 }
-                Err(e) => {
-                    tracing::warn!("⚠️ Error accessing entry: {}", e);
-                    // Continue scanning despite individual entry errors
+
+                // Check for cancellation
+                if self.session.is_cancelled() {
+                    tracing::info!("🛑 Scan cancelled: {}", scan_id);
+
+                    // Hand over whatever was already collected before bailing out
+                    self.flush_batch(scan_id, &mut batch);
+
+                    if let Some(tracker) = &mut tracker {
+                        tracker.save();
+                    }
+
+                    // Send cancellation progress
+                    let (total_files, total_directories, total_size) = self.session.totals();
+                    tx.send(ScanProgress {
+                        scan_id: scan_id.to_string(),
+                        seq: 0,
+                        current_path: String::new(),
+                        root: self.progress_root.clone(),
+                        files_scanned: total_files,
+                        directories_scanned: total_directories,
+                        bytes_scanned: total_size,
+                        bytes_scanned_formatted: format_bytes(total_size, self.config.byte_unit),
+                        progress_percent: percent_of(total_files + total_directories, estimated_total),
+                        estimated_total,
+                        files_per_sec: 0.0,
+                        eta_ms: None,
+                        elapsed_ms: start_time.elapsed().as_millis() as u64,
+                        status: ScanStatus::Cancelled,
+                    });
+
+                    return Err(VeloxError::ScanCancelled);
+                }
+
+                match item {
+                    WalkItem::Entry(entry) => {
+                        // Before any mode below skips the entry, so every mode reports
+                        self.report_walked(
+                            scan_id,
+                            &entry.path,
+                            &mut ticker,
+                            tx,
+                            estimated_total,
+                            start_time,
+                        );
+
+                        if let Some(tracker) = &mut tracker {
+                            // The root directory was counted before the interruption
+                            if entry.depth == 0 && resuming_root {
+                                continue;
+                            }
+                            if entry.depth == 1 {
+                                tracker.enter_subtree(&entry.path, self.session.totals());
+                            }
+                        }
+
+                        if let Some(limiter) = limiter.as_mut().filter(|_| with_metadata) {
+                            limiter.acquire(METADATA_READ_BYTES, &self.session).await;
+                        }
+
+                        let path = entry.path.as_path();
+                        let metadata = match &entry.metadata {
+                            Some(Ok(metadata)) => Some(metadata),
+                            Some(Err(e)) => {
+                                tracing::warn!("⚠️ Failed to read metadata for {}: {}", path.display(), e);
+                                errors.push(io_error_entry(path, e));
+                                None
+                            }
+                            None => None,
+                        };
+
+                        let is_dir = entry.file_type.is_dir();
+                        let is_file = entry.file_type.is_file();
+
+                        // Whichever route reaches a target first counts it; later routes are skipped
+                        if let Some(seen) = &mut seen_targets {
+                            if let Ok(canonical) = std::fs::canonicalize(path) {
+                                if !seen.insert(canonical) {
+                                    symlink_duplicates_skipped += 1;
+                                    continue;
+                                }
+                            }
+                        }
+
+                        let size = metadata.map(|m| m.len()).unwrap_or(0);
+
+                        let modified_at = metadata
+                            .and_then(|m| m.modified().ok())
+                            .map(chrono::DateTime::<Utc>::from);
+
+                        // Size and time filters only ever apply to regular files
+                        let file_matches = !is_file
+                            || (self.within_size_range(size) && self.within_time_window(modified_at));
+
+                        if is_dir {
+                            self.session.record_directory();
+                            depths.add(entry.depth, true);
+                        } else if is_file && (file_matches || self.config.count_filtered) {
+                            self.session.record_file(size);
+                            depths.add(entry.depth, false);
+                        }
+
+                        if !file_matches {
+                            continue;
+                        }
+
+                        // Grouping mode keeps running totals and no entries
+                        if let Some(by_extension) = &mut by_extension {
+                            if is_file {
+                                by_extension.add(path, size);
+                            }
+                            continue;
+                        }
+
+                        if let Some(by_age) = &mut by_age {
+                            if is_file {
+                                by_age.add(modified_at, size);
+                            }
+                            continue;
+                        }
+
+                        if let Some(by_category) = &mut by_category {
+                            if is_file {
+                                by_category.add(path, size);
+                            }
+                            continue;
+                        }
+
+                        // Size-only mode needs nothing beyond the session totals
+                        if self.config.totals_only {
+                            continue;
+                        }
+
+                        // Ranking mode only keeps regular files that make the cut
+                        if let Some(largest) = &mut largest {
+                            if is_file && largest.accepts(size) {
+                                largest.push(self.file_entry(&entry));
+                            }
+                            continue;
+                        }
+
+                        if let Some(newest) = &mut newest {
+                            if let Some(modified) = modified_at.filter(|t| is_file && newest.accepts(*t)) {
+                                newest.push(modified, self.file_entry(&entry));
+                            }
+                            continue;
+                        }
+
+                        if !filter_after_aggregation && !self.config.entry_filter.keeps(is_dir) {
+                            continue;
+                        }
+
+                        if self.config.only_empty_files && !(is_file && size == 0) {
+                            continue;
+                        }
+
+                        // Only files that may have a same-size twin are worth keeping
+                        if repeated_sizes
+                            .as_ref()
+                            .is_some_and(|sizes| !(is_file && sizes.contains(&size)))
+                        {
+                            continue;
+                        }
+
+                        let mut file_entry = self.file_entry(&entry);
+
+                        if self.config.metadata == MetadataLevel::Basic {
+                            file_entry.modified = None;
+                            file_entry.created = None;
+                            file_entry.accessed = None;
+                            file_entry.modified_ms = None;
+                            file_entry.created_ms = None;
+                            file_entry.accessed_ms = None;
+                        }
+
+                        if let Some(metadata) = metadata.filter(|_| self.config.read_permissions) {
+                            file_entry.read_permissions(metadata);
+                        }
+
+                        if let Some(metadata) = metadata.filter(|_| self.config.detect_sparse) {
+                            file_entry.read_sparseness(metadata);
+                        }
+
+                        // Quick scans skip the extra stat needed to follow the link
+                        if metadata.is_some() {
+                            file_entry.check_link_target(path);
+                            let broken = file_entry.is_broken == Some(true);
+                            if broken && self.config.report_broken_symlinks {
+                                errors.push(ScanErrorEntry {
+                                    path: path.to_string_lossy().to_string(),
+                                    message: "Symlink target is missing".to_string(),
+                                    kind: "broken_symlink".to_string(),
+                                });
+                            }
+                        }
+
+                        if self.config.detect_mime && is_file {
+                            file_entry.mime_type = sniff_mime(path);
+                        }
+
+                        if self.config.hash_files && is_file && self.within_hash_limit(size) {
+                            if let Some(limiter) = &mut limiter {
+                                limiter.acquire(size, &self.session).await;
+                            }
+
+                            let algorithm = self.config.hash_algorithm;
+                            match digest_file_with_retry(path, algorithm, self.config.io_retries)
+                                .await
+                            {
+                                Ok(digest) => file_entry.hash = Some(digest),
+                                Err(e) => {
+                                    tracing::warn!("⚠️ Failed to hash {}: {}", path.display(), e);
+                                    errors.push(ScanErrorEntry {
+                                        path: path.to_string_lossy().to_string(),
+                                        message: e.to_string(),
+                                        kind: "hash_failed".to_string(),
+                                    });
+                                }
+                            }
+                        }
+
+                        match self.config.stream_batch_size {
+                            Some(batch_size) => {
+                                batch.push(file_entry);
+                                if batch.len() >= batch_size {
+                                    self.flush_batch(scan_id, &mut batch);
+                                }
+                            }
+                            None => entries.push(file_entry),
+                        }
+
+                        let collected = self.collected.fetch_add(1, Ordering::Relaxed) + 1;
+                        if self.config.max_entries.is_some_and(|max| collected >= max) {
+                            tracing::warn!("✂️ Entry cap of {} reached, stopping scan: {}", collected, scan_id);
+                            truncated = true;
+                            break;
+                        }
+                    }
+                    WalkItem::Error(error) => {
+                        if error.kind == ACCESS_DENIED_KIND {
+                            tracing::warn!("🔒 {}", error.message);
+                        } else {
+                            tracing::warn!("⚠️ Error accessing entry: {}", error.message);
+                        }
+                        // Continue scanning despite individual entry errors
+                        errors.push(error);
+                    }
                 }
             }
+
+            let totals = self.session.totals();
+            let (files, directories, bytes) = totals;
+            let total_size = bytes - before.2;
+            let summary = RootSummary {
+                path: root,
+                total_files: files - before.0,
+                total_directories: directories - before.1,
+                total_size,
+                total_size_formatted: format_bytes(total_size, self.config.byte_unit),
+            };
+
+            if let Some(tracker) = tracker.as_mut().filter(|_| !truncated) {
+                tracker.finish_root(summary.clone(), totals);
+            }
+            root_summaries.push(summary);
+
+            // Remaining roots are skipped once the entry cap is hit
+            if truncated {
+                break;
+            }
+        }
+
+        // Flush the trailing partial batch
+        self.flush_batch(scan_id, &mut batch);
+
+        if let Some(tracker) = tracker {
+            tracker.discard();
+        }
+
+        if let Some(largest) = largest {
+            entries = largest.into_sorted_vec();
+        }
+
+        if let Some(newest) = newest {
+            entries = newest.into_sorted_vec();
+        }
+
+        // Streamed batches are already gone, so aggregation only covers collected entries
+        if self.config.aggregate_dir_sizes {
+            self.enter_phase(scan_id, ScanPhase::Aggregating, None);
+            aggregate_directory_sizes(&mut entries, self.config.byte_unit);
+
+            let entry_filter = self.config.entry_filter;
+            entries.retain(|entry| entry_filter.keeps(entry.is_directory));
         }
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
+        let status = if truncated {
+            ScanStatus::Truncated
+        } else {
+            ScanStatus::Completed
+        };
 
         // Send final progress
+        let (total_files, total_directories, total_size) = self.session.totals();
         tx.send(ScanProgress {
             scan_id: scan_id.to_string(),
+            seq: 0,
             current_path: String::new(),
+            root: self.progress_root.clone(),
             files_scanned: total_files,
             directories_scanned: total_directories,
             bytes_scanned: total_size,
-            bytes_scanned_formatted: human_bytes(total_size as f64),
+            bytes_scanned_formatted: format_bytes(total_size, self.config.byte_unit),
             progress_percent: 100.0,
             estimated_total: Some(total_files + total_directories),
+            files_per_sec: 0.0,
+            eta_ms: None,
             elapsed_ms: duration_ms,
-            status: ScanStatus::Completed,
-        }).await.ok();
+            status: status.clone(),
+        });
 
         Ok(ScanResult {
             scan_id: scan_id.to_string(),
-            root_path: root_path.to_string(),
+            root_path: roots.join(", "),
+            roots: root_summaries,
             total_files,
             total_directories,
             total_size,
-            total_size_formatted: human_bytes(total_size as f64),
+            total_size_formatted: format_bytes(total_size, self.config.byte_unit),
             entries,
+            errors,
+            extension_stats: by_extension.map(|totals| totals.into_stats(self.config.byte_unit)),
+            age_buckets: by_age.map(|histogram| histogram.into_buckets(self.config.byte_unit)),
+            depth_distribution: depths.into_buckets(),
+            category_stats: by_category.map(|totals| totals.into_summaries(self.config.byte_unit)),
             duration_ms,
             completed_at: Utc::now().to_rfc3339(),
-            status: ScanStatus::Completed,
+            truncated,
+            symlink_duplicates_skipped,
+            pruned_directories: pruned_dirs.load(Ordering::Relaxed),
+            hash_algorithm: self.config.hash_files.then_some(self.config.hash_algorithm),
+            status,
+            request: None,
         })
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::{NullSink, SinkEvent, VecSink};
+    use std::fs;
+
+    /// Size of `sub/big.bin`, large enough to span many read chunks
+    const BIG_FILE_SIZE: usize = 3 * 1024 * 1024;
+
+    /// Content bytes of the visible regular files
+    const VISIBLE_BYTES: u64 = 3 + 5 + 6 + BIG_FILE_SIZE as u64;
+
+    /// root/{a.txt, .hidden, empty.dat, link -> sub, sub/{b.bin, big.bin, deep/c.txt}}
+    /// with the symlink on Unix only
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("sub/deep")).unwrap();
+        fs::write(root.join("a.txt"), "abc").unwrap();
+        fs::write(root.join(".hidden"), "1234").unwrap();
+        fs::write(root.join("empty.dat"), "").unwrap();
+        fs::write(root.join("sub/b.bin"), "12345").unwrap();
+        fs::write(root.join("sub/big.bin"), vec![7u8; BIG_FILE_SIZE]).unwrap();
+        fs::write(root.join("sub/deep/c.txt"), "123456").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(root.join("sub"), root.join("link")).unwrap();
+        dir
+    }
+
+    async fn run<S: ProgressSink>(
+        root: &Path,
+        sink: S,
+        config: ScanConfig,
+    ) -> VeloxResult<ScanResult> {
+        let session = Arc::new(ScanSession::new(root.to_string_lossy().to_string()));
+        let scanner = DirectoryScanner::new(session, sink, config);
+        let roots = scanner.roots();
+        let queue = ProgressQueue::new(16);
+        scanner
+            .execute_scan("test", &roots, &queue, Instant::now())
+            .await
+    }
+
+    #[tokio::test]
+    async fn totals_cover_the_whole_tree() {
+        let dir = fixture();
+        let result = run(dir.path(), NullSink, ScanConfig::default())
+            .await
+            .unwrap();
+
+        // The empty file counts as a file with no bytes
+        assert_eq!(result.total_files, 5);
+        assert_eq!(result.total_directories, 3);
+        assert_eq!(result.total_size, VISIBLE_BYTES);
+        let empty = result
+            .entries
+            .iter()
+            .find(|e| e.name == "empty.dat")
+            .unwrap();
+        assert!(empty.is_file && empty.size == 0);
+        assert!(result.errors.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinks_are_listed_but_not_followed() {
+        let dir = fixture();
+        let result = run(dir.path(), NullSink, ScanConfig::default())
+            .await
+            .unwrap();
+
+        let link = result.entries.iter().find(|e| e.name == "link").unwrap();
+        assert!(link.is_symlink && !link.is_directory && !link.is_file);
+        // Nothing below the link is walked a second time
+        let under_link = dir.path().join("link");
+        assert!(!result
+            .entries
+            .iter()
+            .any(|e| Path::new(&e.path).starts_with(&under_link) && e.name != "link"));
+        assert_eq!(result.entries.len(), 9);
+    }
+
+    #[tokio::test]
+    async fn include_hidden_adds_dotfiles() {
+        let dir = fixture();
+        let config = ScanConfig {
+            include_hidden: true,
+            ..ScanConfig::default()
+        };
+        let result = run(dir.path(), NullSink, config).await.unwrap();
+
+        assert_eq!(result.total_files, 6);
+        assert_eq!(result.total_size, VISIBLE_BYTES + 4);
+    }
+
+    #[tokio::test]
+    async fn max_depth_stops_before_deeper_entries() {
+        let dir = fixture();
+        let config = ScanConfig {
+            max_depth: 1,
+            ..ScanConfig::default()
+        };
+        let result = run(dir.path(), NullSink, config).await.unwrap();
+
+        assert_eq!(result.total_files, 2);
+        assert_eq!(result.total_directories, 2);
+        assert_eq!(result.total_size, 3);
+        assert!(result.entries.iter().all(|entry| entry.depth <= 1));
+    }
+
+    #[test]
+    fn invalid_name_regex_is_rejected_when_building_the_config() {
+        let request: ScanRequest = serde_json::from_value(serde_json::json!({
+            "path": "/",
+            "includeHidden": false,
+            "followSymlinks": false,
+            "nameRegex": "report(",
+        }))
+        .unwrap();
+
+        let result = ScanConfig::from_request(&request, &VeloxConfig::default());
+        assert!(matches!(result, Err(VeloxError::InvalidPattern(_))));
+    }
+
+    #[tokio::test]
+    async fn approximate_dedup_only_collects_repeated_sizes() {
+        let dir = fixture();
+        fs::write(dir.path().join("twin.txt"), "xyz").unwrap();
+        let config = ScanConfig {
+            dedup_memory_mode: DedupMode::Approximate,
+            ..ScanConfig::default()
+        };
+        let result = run(dir.path(), NullSink, config).await.unwrap();
+
+        // Totals still cover the whole tree
+        assert_eq!(result.total_files, 6);
+        let mut names: Vec<&str> = result.entries.iter().map(|e| e.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["a.txt", "twin.txt"]);
+    }
+
+    /// Cancels the scan as soon as the first batch arrives
+    #[derive(Clone)]
+    struct CancelOnBatch {
+        session: Arc<ScanSession>,
+        inner: VecSink,
+    }
+
+    impl ProgressSink for CancelOnBatch {
+        fn emit_progress(&self, progress: &ScanProgress) {
+            self.inner.emit_progress(progress);
+        }
+
+        fn emit_phase(&self, phase: &ScanPhaseEvent) {
+            self.inner.emit_phase(phase);
+        }
+
+        fn emit_batch(&self, batch: &ScanBatch) {
+            self.inner.emit_batch(batch);
+            self.session.cancel();
+        }
+
+        fn emit_complete(&self, result: &ScanResult) {
+            self.inner.emit_complete(result);
+        }
+
+        fn emit_error(&self, scan_id: &str, error: &VeloxError) {
+            self.inner.emit_error(scan_id, error);
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelling_mid_walk_stops_the_scan() {
+        let dir = fixture();
+        let session = Arc::new(ScanSession::new(dir.path().to_string_lossy().to_string()));
+        let sink = CancelOnBatch {
+            session: session.clone(),
+            inner: VecSink::default(),
+        };
+        let config = ScanConfig {
+            stream_batch_size: Some(1),
+            ..ScanConfig::default()
+        };
+        let scanner = DirectoryScanner::new(session, sink.clone(), config);
+        let roots = scanner.roots();
+        let queue = ProgressQueue::new(16);
+        let result = scanner
+            .execute_scan("test", &roots, &queue, Instant::now())
+            .await;
+
+        assert!(matches!(result, Err(VeloxError::ScanCancelled)));
+        let batches = sink
+            .inner
+            .events()
+            .into_iter()
+            .filter(|e| matches!(e, SinkEvent::Batch(_)));
+        assert_eq!(batches.count(), 1);
+    }
+}
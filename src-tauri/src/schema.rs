@@ -0,0 +1,29 @@
+// VELOX CORE - Type Schemas
+// JSON Schemas for the payloads exchanged with the frontend
+
+use schemars::schema_for;
+use serde_json::{Map, Value};
+
+use crate::error::{ErrorResponse, VeloxError, VeloxResult};
+use crate::types::{FileEntry, ScanProgress, ScanRequest, ScanResult};
+
+/// Schemas for the public IPC types, keyed by Rust type name. Each value is
+/// a standalone draft-07 schema carrying its own `definitions`.
+pub fn type_schemas() -> VeloxResult<Value> {
+    let schemas = [
+        ("ScanRequest", schema_for!(ScanRequest)),
+        ("ScanResult", schema_for!(ScanResult)),
+        ("ScanProgress", schema_for!(ScanProgress)),
+        ("FileEntry", schema_for!(FileEntry)),
+        ("ErrorResponse", schema_for!(ErrorResponse)),
+    ];
+
+    let mut bundle = Map::new();
+    for (name, schema) in schemas {
+        let value =
+            serde_json::to_value(schema).map_err(|e| VeloxError::Serialization(e.to_string()))?;
+        bundle.insert(name.to_string(), value);
+    }
+
+    Ok(Value::Object(bundle))
+}
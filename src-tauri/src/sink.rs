@@ -0,0 +1,179 @@
+// VELOX CORE - Progress Sinks
+// Where scanner events go: the Tauri window, a file, or nowhere for headless use
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tauri::Window;
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::export::NdjsonWriter;
+use crate::types::{ExportStats, ScanBatch, ScanPhaseEvent, ScanProgress, ScanResult, ScanSession};
+
+/// Receiver for everything a scan reports while it runs
+pub trait ProgressSink: Clone + Send + Sync + 'static {
+    fn emit_progress(&self, progress: &ScanProgress);
+    fn emit_phase(&self, phase: &ScanPhaseEvent);
+    fn emit_batch(&self, batch: &ScanBatch);
+    fn emit_complete(&self, result: &ScanResult);
+    fn emit_error(&self, scan_id: &str, error: &VeloxError);
+}
+
+impl ProgressSink for Window {
+    fn emit_progress(&self, progress: &ScanProgress) {
+        self.emit("velox:scan:progress", progress).ok();
+    }
+
+    fn emit_phase(&self, phase: &ScanPhaseEvent) {
+        self.emit("velox:scan:phase", phase).ok();
+    }
+
+    fn emit_batch(&self, batch: &ScanBatch) {
+        self.emit("velox:scan:batch", batch).ok();
+    }
+
+    fn emit_complete(&self, result: &ScanResult) {
+        self.emit("velox:scan:complete", result).ok();
+    }
+
+    fn emit_error(&self, scan_id: &str, error: &VeloxError) {
+        self.emit(
+            "velox:scan:error",
+            serde_json::json!({
+                "scanId": scan_id,
+                "error": error.to_string()
+            }),
+        )
+        .ok();
+    }
+}
+
+/// Writes streamed batches to an NDJSON file instead of emitting them and
+/// forwards every other event to `inner`. A failed write cancels the scan.
+#[derive(Clone)]
+pub struct NdjsonFileSink<S> {
+    inner: S,
+    writer: Arc<Mutex<NdjsonWriter>>,
+    session: Arc<ScanSession>,
+    error: Arc<Mutex<Option<VeloxError>>>,
+}
+
+impl<S: ProgressSink> NdjsonFileSink<S> {
+    pub fn new(inner: S, writer: NdjsonWriter, session: Arc<ScanSession>) -> Self {
+        Self {
+            inner,
+            writer: Arc::new(Mutex::new(writer)),
+            session,
+            error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Flush the file; reports the write error that stopped the scan, if any
+    pub fn finish(&self) -> VeloxResult<ExportStats> {
+        if let Some(error) = self.error.lock().take() {
+            return Err(error);
+        }
+        self.writer.lock().finish()
+    }
+}
+
+impl<S: ProgressSink> ProgressSink for NdjsonFileSink<S> {
+    fn emit_progress(&self, progress: &ScanProgress) {
+        self.inner.emit_progress(progress);
+    }
+
+    fn emit_phase(&self, phase: &ScanPhaseEvent) {
+        self.inner.emit_phase(phase);
+    }
+
+    fn emit_batch(&self, batch: &ScanBatch) {
+        let mut error = self.error.lock();
+        if error.is_some() {
+            return;
+        }
+
+        let mut writer = self.writer.lock();
+        for entry in &batch.entries {
+            if let Err(e) = writer.write_entry(entry) {
+                tracing::error!("❌ Stopping scan {}: {}", batch.scan_id, e);
+                self.session.cancel();
+                *error = Some(e);
+                return;
+            }
+        }
+    }
+
+    fn emit_complete(&self, result: &ScanResult) {
+        self.inner.emit_complete(result);
+    }
+
+    fn emit_error(&self, scan_id: &str, error: &VeloxError) {
+        self.inner.emit_error(scan_id, error);
+    }
+}
+
+/// Discards every event
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSink;
+
+impl ProgressSink for NullSink {
+    fn emit_progress(&self, _progress: &ScanProgress) {}
+    fn emit_phase(&self, _phase: &ScanPhaseEvent) {}
+    fn emit_batch(&self, _batch: &ScanBatch) {}
+    fn emit_complete(&self, _result: &ScanResult) {}
+    fn emit_error(&self, _scan_id: &str, _error: &VeloxError) {}
+}
+
+/// Event captured by `VecSink`
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub enum SinkEvent {
+    Progress(ScanProgress),
+    Phase(ScanPhaseEvent),
+    Batch(ScanBatch),
+    Complete(ScanResult),
+    Error { scan_id: String, message: String },
+}
+
+/// Records every event in order so callers can inspect them afterwards
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct VecSink {
+    events: Arc<Mutex<Vec<SinkEvent>>>,
+}
+
+#[allow(dead_code)]
+impl VecSink {
+    /// Copy of the events received so far
+    pub fn events(&self) -> Vec<SinkEvent> {
+        self.events.lock().clone()
+    }
+}
+
+impl ProgressSink for VecSink {
+    fn emit_progress(&self, progress: &ScanProgress) {
+        self.events
+            .lock()
+            .push(SinkEvent::Progress(progress.clone()));
+    }
+
+    fn emit_phase(&self, phase: &ScanPhaseEvent) {
+        self.events.lock().push(SinkEvent::Phase(phase.clone()));
+    }
+
+    fn emit_batch(&self, batch: &ScanBatch) {
+        self.events.lock().push(SinkEvent::Batch(batch.clone()));
+    }
+
+    fn emit_complete(&self, result: &ScanResult) {
+        self.events.lock().push(SinkEvent::Complete(result.clone()));
+    }
+
+    fn emit_error(&self, scan_id: &str, error: &VeloxError) {
+        self.events.lock().push(SinkEvent::Error {
+            scan_id: scan_id.to_string(),
+            message: error.to_string(),
+        });
+    }
+}
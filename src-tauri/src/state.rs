@@ -2,11 +2,19 @@
 // Thread-safe state using parking_lot for maximum performance
 
 use chrono::{DateTime, Utc};
-use parking_lot::RwLock;
-use std::collections::HashMap;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sysinfo::System;
 
-use crate::types::{ScanSession, ScanStatus};
+use crate::error::{VeloxError, VeloxResult};
+use crate::logs::{LogBuffer, LOG_BUFFER_CAPACITY};
+use crate::types::{ByteUnit, ScanProfile, ScanResult, ScanSession, ScanSnapshot, ScanSummary};
+use crate::watcher::DirectoryWatch;
 
 /// Global managed state for VELOX CORE
 pub struct VeloxState {
@@ -18,16 +26,60 @@ pub struct VeloxState {
     
     /// Configuration
     pub config: RwLock<VeloxConfig>,
+
+    /// Recently finished scans, newest first
+    pub history: RwLock<VecDeque<ScanSummary>>,
+
+    /// Full results of recently completed scans, most recently used first
+    pub results: RwLock<VecDeque<Arc<ScanResult>>>,
+
+    /// Active filesystem watches keyed by watch ID
+    pub watches: Mutex<HashMap<String, DirectoryWatch>>,
+
+    /// Kept between calls so CPU usage can be measured across refreshes
+    pub system: Mutex<System>,
+
+    /// Recent log lines captured from tracing
+    pub logs: LogBuffer,
+
+    /// Saved scan profiles, sorted by name
+    pub profiles: RwLock<Vec<ScanProfile>>,
+
+    /// Held from reading the profile list until the updated list is on disk
+    /// and swapped in, so concurrent saves and deletes never lose each other's
+    /// changes. Readers only take `profiles`.
+    pub profile_writes: tokio::sync::Mutex<()>,
+
+    /// Scans registered since startup
+    pub scans_started: AtomicU64,
+
+    /// Scans that ended without being cancelled, including failed ones
+    pub scans_completed: AtomicU64,
+
+    /// Scans that ended cancelled, by the user, a memory limit or the reaper
+    pub scans_cancelled: AtomicU64,
+
+    /// Most scans that were active at the same time
+    pub peak_active_scans: AtomicU64,
 }
 
 /// Application configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct VeloxConfig {
     pub max_concurrent_scans: usize,
     pub default_max_depth: usize,
     pub include_hidden_default: bool,
     pub follow_symlinks_default: bool,
     pub progress_emit_interval_ms: u64,
+    pub history_limit: usize,
+    pub result_cache_limit: usize,
+    /// Scans idle for longer than this are cancelled and dropped; 0 disables
+    pub scan_timeout_ms: u64,
+    /// Scans stop once the app's resident memory passes this many bytes; 0 disables
+    pub memory_limit_bytes: u64,
+    /// Decimal (KB) or binary (KiB) units for every formatted size
+    pub byte_unit: ByteUnit,
 }
 
 impl Default for VeloxConfig {
@@ -38,16 +90,70 @@ impl Default for VeloxConfig {
             include_hidden_default: false,
             follow_symlinks_default: false,
             progress_emit_interval_ms: 50, // 20 updates per second max
+            history_limit: 50,
+            result_cache_limit: 5,
+            scan_timeout_ms: 60 * 60 * 1000,
+            memory_limit_bytes: 0,
+            byte_unit: ByteUnit::default(),
         }
     }
 }
 
+impl VeloxConfig {
+    /// Load configuration from a JSON file.
+    /// Missing or malformed files fall back to defaults instead of failing startup.
+    pub fn load_from_disk(path: &Path) -> Self {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tracing::debug!("No saved config at {}, using defaults", path.display());
+                return Self::default();
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ Failed to read config {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            tracing::warn!("⚠️ Malformed config {}, using defaults: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    /// Write configuration as JSON, creating the parent directory if needed
+    pub fn save_to_disk(&self, path: &Path) -> VeloxResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| VeloxError::io_at(parent, e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| VeloxError::Serialization(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| VeloxError::io_at(path, e))?;
+
+        tracing::debug!("Saved config to {}", path.display());
+        Ok(())
+    }
+}
+
 impl VeloxState {
-    pub fn new() -> Self {
+    /// `logs` is the buffer the tracing layer fills
+    pub fn new(logs: LogBuffer) -> Self {
         Self {
             started_at: Utc::now(),
             active_scans: RwLock::new(HashMap::new()),
             config: RwLock::new(VeloxConfig::default()),
+            history: RwLock::new(VecDeque::new()),
+            results: RwLock::new(VecDeque::new()),
+            watches: Mutex::new(HashMap::new()),
+            system: Mutex::new(System::new()),
+            logs,
+            profiles: RwLock::new(Vec::new()),
+            profile_writes: tokio::sync::Mutex::new(()),
+            scans_started: AtomicU64::new(0),
+            scans_completed: AtomicU64::new(0),
+            scans_cancelled: AtomicU64::new(0),
+            peak_active_scans: AtomicU64::new(0),
         }
     }
 
@@ -58,12 +164,21 @@ impl VeloxState {
             .num_milliseconds() as u64
     }
 
-    /// Register a new scan session
-    pub fn register_scan(&self, session: ScanSession) -> String {
-        let id = session.id.to_string();
+    /// Register a new scan session unless the concurrency limit is reached.
+    /// The check and insert happen under one write lock so parallel requests
+    /// can't both slip past the limit.
+    pub fn register_scan(&self, session: ScanSession) -> VeloxResult<String> {
+        let limit = self.config.read().max_concurrent_scans;
         let mut scans = self.active_scans.write();
+        if scans.len() >= limit {
+            return Err(VeloxError::TooManyScans(limit));
+        }
+
+        let id = session.id.to_string();
         scans.insert(id.clone(), Arc::new(session));
-        id
+        self.scans_started.fetch_add(1, Ordering::Relaxed);
+        self.peak_active_scans.fetch_max(scans.len() as u64, Ordering::Relaxed);
+        Ok(id)
     }
 
     /// Get a scan session by ID
@@ -72,16 +187,80 @@ impl VeloxState {
         scans.get(scan_id).cloned()
     }
 
-    /// Remove a completed scan session
+    /// Remove a finished scan session, counting it as completed or cancelled
     pub fn remove_scan(&self, scan_id: &str) {
         let mut scans = self.active_scans.write();
-        scans.remove(scan_id);
+        if let Some(session) = scans.remove(scan_id) {
+            self.count_finished(&session);
+        }
     }
 
-    /// Get count of active scans
+    fn count_finished(&self, session: &ScanSession) {
+        let counter = if session.is_cancelled() {
+            &self.scans_cancelled
+        } else {
+            &self.scans_completed
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get count of active scans.
+    /// Sessions are removed as soon as they finish, so every registered
+    /// session counts as active.
     pub fn active_scan_count(&self) -> usize {
         let scans = self.active_scans.read();
-        scans.values().filter(|s| s.status == ScanStatus::Scanning).count()
+        scans.len()
+    }
+
+    /// Record a finished scan, evicting the oldest entries beyond the history limit
+    pub fn record_scan(&self, summary: ScanSummary) {
+        let limit = self.config.read().history_limit;
+        let mut history = self.history.write();
+        history.push_front(summary);
+        history.truncate(limit);
+    }
+
+    /// Get recorded scan summaries, newest first
+    pub fn scan_history(&self) -> Vec<ScanSummary> {
+        let history = self.history.read();
+        history.iter().cloned().collect()
+    }
+
+    /// Retain a completed result for later retrieval.
+    /// Beyond the cache limit the least recently used results are evicted.
+    pub fn store_result(&self, result: ScanResult) {
+        let limit = self.config.read().result_cache_limit;
+        let mut results = self.results.write();
+        results.retain(|r| r.scan_id != result.scan_id);
+        results.push_front(Arc::new(result));
+        results.truncate(limit);
+    }
+
+    /// Get a retained result by scan ID, marking it most recently used
+    pub fn get_result(&self, scan_id: &str) -> Option<Arc<ScanResult>> {
+        let mut results = self.results.write();
+        let index = results.iter().position(|r| r.scan_id == scan_id)?;
+        let result = results.remove(index)?;
+        results.push_front(result.clone());
+        Some(result)
+    }
+
+    /// Drop every retained result, returning how many were freed
+    pub fn clear_results(&self) -> usize {
+        let mut results = self.results.write();
+        let cleared = results.len();
+        results.clear();
+        cleared
+    }
+
+    /// Keep a watch alive until it is stopped
+    pub fn add_watch(&self, watch_id: String, watch: DirectoryWatch) {
+        self.watches.lock().insert(watch_id, watch);
+    }
+
+    /// Stop a watch by ID; dropping it tears down the OS watcher
+    pub fn remove_watch(&self, watch_id: &str) -> bool {
+        self.watches.lock().remove(watch_id).is_some()
     }
 
     /// Cancel a scan by ID
@@ -93,11 +272,130 @@ impl VeloxState {
             false
         }
     }
+
+    /// Snapshots of every in-flight scan; never waits on the scanners
+    pub fn active_scan_snapshots(&self) -> Vec<ScanSnapshot> {
+        let scans = self.active_scans.read();
+        scans.values().map(|session| session.snapshot()).collect()
+    }
+
+    /// Cancel every active scan, returning how many were not already cancelled
+    pub fn cancel_all(&self) -> usize {
+        let scans = self.active_scans.read();
+        let mut cancelled = 0;
+        for session in scans.values().filter(|session| !session.is_cancelled()) {
+            session.cancel();
+            cancelled += 1;
+        }
+        cancelled
+    }
+
+    /// Cancel and remove sessions that have shown no activity within
+    /// `scan_timeout_ms`, such as ones whose scan task panicked. A paused scan
+    /// is idle too, so a pause longer than the timeout ends it.
+    /// Returns how many sessions were reaped.
+    pub fn reap_stale_scans(&self) -> usize {
+        let timeout_ms = self.config.read().scan_timeout_ms;
+        if timeout_ms == 0 {
+            return 0;
+        }
+
+        let now = Utc::now();
+        let mut scans = self.active_scans.write();
+        let before = scans.len();
+        scans.retain(|scan_id, session| {
+            let idle_ms = session.idle_ms(now);
+            if idle_ms < timeout_ms {
+                return true;
+            }
+
+            session.cancel();
+            self.count_finished(session);
+            tracing::warn!(
+                "🧹 Reaped scan {} ({}) after {}ms without activity",
+                scan_id,
+                session.root_path,
+                idle_ms
+            );
+            false
+        });
+        before - scans.len()
+    }
+
+    /// Block until every active session has been removed or the timeout
+    /// elapses. Returns true when all scans drained in time.
+    pub fn wait_for_scans(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.active_scan_count() > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        true
+    }
+
+    /// Pause a scan by ID
+    pub fn pause_scan(&self, scan_id: &str) -> bool {
+        if let Some(session) = self.get_scan(scan_id) {
+            session.pause();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Resume a paused scan by ID
+    pub fn resume_scan(&self, scan_id: &str) -> bool {
+        if let Some(session) = self.get_scan(scan_id) {
+            session.resume();
+            true
+        } else {
+            false
+        }
+    }
 }
 
 impl Default for VeloxState {
+    /// State with a log buffer that no tracing layer writes to
     fn default() -> Self {
-        Self::new()
+        Self::new(LogBuffer::new(LOG_BUFFER_CAPACITY))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_scan_enforces_the_concurrency_limit() {
+        let state = VeloxState::default();
+        state.config.write().max_concurrent_scans = 2;
+
+        let first = state.register_scan(ScanSession::new("/a".into())).unwrap();
+        state.register_scan(ScanSession::new("/b".into())).unwrap();
+        assert!(matches!(
+            state.register_scan(ScanSession::new("/c".into())),
+            Err(VeloxError::TooManyScans(2))
+        ));
+
+        // A finished scan frees its slot
+        state.remove_scan(&first);
+        assert!(state.register_scan(ScanSession::new("/c".into())).is_ok());
+        assert_eq!(state.active_scan_count(), 2);
+    }
+
+    #[test]
+    fn cancel_all_only_counts_scans_still_running() {
+        let state = VeloxState::default();
+        let first = state.register_scan(ScanSession::new("/a".into())).unwrap();
+        state.register_scan(ScanSession::new("/b".into())).unwrap();
+        state.cancel_scan(&first);
+
+        assert_eq!(state.cancel_all(), 1);
+        let scans = state.active_scans.read();
+        assert!(scans.values().all(|session| session.is_cancelled()));
+        drop(scans);
+        assert_eq!(state.cancel_all(), 0);
+    }
+}
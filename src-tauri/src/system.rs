@@ -0,0 +1,76 @@
+// VELOX CORE - System Probes
+// Host resources the UI needs before choosing what and how to scan
+
+use std::path::Path;
+
+use sysinfo::{Disks, System};
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::{DiskUsage, VolumeInfo};
+
+/// Enumerate mounted volumes, re-reading the disk list on every call.
+/// Returns an empty list when the platform reports no disks.
+pub fn list_volumes() -> Vec<VolumeInfo> {
+    let disks = Disks::new_with_refreshed_list();
+
+    disks
+        .list()
+        .iter()
+        .map(|disk| VolumeInfo {
+            name: disk.name().to_string_lossy().to_string(),
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            total_bytes: disk.total_space(),
+            available_bytes: disk.available_space(),
+            file_system: disk.file_system().to_string_lossy().to_string(),
+            is_removable: disk.is_removable(),
+        })
+        .collect()
+}
+
+/// Capacity of the volume holding `path`, chosen by the longest mount point
+/// that contains it. Symlinks are resolved first so the real volume is found.
+pub fn disk_usage_for(path: &Path) -> VeloxResult<DiskUsage> {
+    let resolved = path
+        .canonicalize()
+        .map_err(|_| VeloxError::InvalidPath(path.display().to_string()))?;
+
+    let disks = Disks::new_with_refreshed_list();
+    let disk = disks
+        .list()
+        .iter()
+        .filter(|disk| resolved.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .ok_or_else(|| {
+            VeloxError::InvalidPath(format!("{} is not on a known volume", path.display()))
+        })?;
+
+    // sysinfo only reports space available to the current user, so free and
+    // available are the same figure
+    Ok(DiskUsage {
+        mount_point: disk.mount_point().to_string_lossy().to_string(),
+        total_bytes: disk.total_space(),
+        free_bytes: disk.available_space(),
+        available_bytes: disk.available_space(),
+    })
+}
+
+/// Memory and CPU load at the time of the call
+pub struct ResourceUsage {
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub cpu_usage_percent: f32,
+}
+
+/// Refresh and read memory and CPU figures from a long-lived `System`.
+/// CPU usage is measured between consecutive refreshes, so the first
+/// call after startup reports 0%.
+pub fn sample_resources(system: &mut System) -> ResourceUsage {
+    system.refresh_memory();
+    system.refresh_cpu_usage();
+
+    ResourceUsage {
+        total_memory_bytes: system.total_memory(),
+        available_memory_bytes: system.available_memory(),
+        cpu_usage_percent: system.global_cpu_info().cpu_usage(),
+    }
+}
@@ -1,10 +1,18 @@
 // VELOX CORE - Type Definitions
 // Strict type contracts between Rust and TypeScript
 
+use std::ffi::OsStr;
+use std::fs::{self, FileType, Metadata};
+use std::io;
+use std::path::Path;
+
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::classify::{classify_extension, file_extension};
+
 /// Unique identifier for scan sessions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct ScanId(pub Uuid);
@@ -28,9 +36,11 @@ impl std::fmt::Display for ScanId {
 }
 
 /// File entry metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FileEntry {
+    /// Empty unless the scan was asked for ids, which are then unique for
+    /// the life of the process
     pub id: String,
     pub name: String,
     pub path: String,
@@ -39,53 +49,820 @@ pub struct FileEntry {
     pub is_directory: bool,
     pub is_file: bool,
     pub is_symlink: bool,
+    /// Without the dot; compound forms such as `tar.gz` are kept whole
     pub extension: Option<String>,
     pub modified: Option<String>,
     pub created: Option<String>,
+    /// Last access time. Mounts with `noatime` don't track it, and `relatime`
+    /// only updates it occasionally, so treat it as a hint; `None` where the
+    /// platform reports nothing.
+    pub accessed: Option<String>,
+    /// Unix epoch millis, filled instead of `modified`/`created`/`accessed`
+    /// when the scan asked for `TimestampFormat::UnixMillis`
+    pub modified_ms: Option<i64>,
+    pub created_ms: Option<i64>,
+    pub accessed_ms: Option<i64>,
     pub depth: usize,
     pub children_count: Option<u64>,
+    /// Hex digest; the algorithm is recorded on the enclosing `ScanResult`
+    pub hash: Option<String>,
+    pub mime_type: Option<String>,
+    /// Coarse type derived from the extension; directories are `Other`
+    pub category: FileCategory,
+    /// Unix mode bits (permissions plus setuid/setgid/sticky). This and the
+    /// fields below are only read when requested, and stay `None` off Unix.
+    pub permissions: Option<u32>,
+    /// `ls`-style rendering of `permissions`, e.g. `rwxr-xr-x`
+    pub permissions_string: Option<String>,
+    pub owner_uid: Option<u32>,
+    pub owner_gid: Option<u32>,
+    /// Regular file with fewer bytes allocated on disk than its logical size.
+    /// Only checked when requested, and `None` off Unix. Filesystems that
+    /// compress data transparently also report such files as sparse.
+    pub is_sparse: Option<bool>,
+    /// Symlink whose target is missing. Only checked for symlinks, and only
+    /// when the scan reads metadata.
+    pub is_broken: Option<bool>,
+}
+
+/// Broad file type used for grouping and icons
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FileCategory {
+    Image,
+    Video,
+    Audio,
+    Document,
+    Archive,
+    Code,
+    Executable,
+    Other,
+}
+
+/// How `FileEntry` timestamps are represented
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// RFC3339 strings in `modified`/`created`/`accessed`
+    #[default]
+    Rfc3339,
+    /// Epoch millis in `modified_ms`/`created_ms`/`accessed_ms`
+    UnixMillis,
+}
+
+impl TimestampFormat {
+    /// String and numeric forms of a timestamp; only the chosen one is set
+    fn render(self, time: Option<DateTime<Utc>>) -> (Option<String>, Option<i64>) {
+        match self {
+            Self::Rfc3339 => (time.map(|t| t.to_rfc3339()), None),
+            Self::UnixMillis => (None, time.map(|t| t.timestamp_millis())),
+        }
+    }
+}
+
+/// Base of the human-readable sizes in `*_formatted` fields
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteUnit {
+    /// Powers of 1000: KB, MB, GB
+    Decimal,
+    /// Powers of 1024: KiB, MiB, GiB
+    #[default]
+    Binary,
+}
+
+/// Render a byte count with one decimal place, dropping a trailing `.0`,
+/// e.g. "1.5 MiB" or "2 KB"
+pub fn format_bytes(bytes: u64, unit: ByteUnit) -> String {
+    let (base, suffixes) = match unit {
+        ByteUnit::Decimal => (1000.0, ["B", "KB", "MB", "GB", "TB", "PB", "EB"]),
+        ByteUnit::Binary => (1024.0, ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]),
+    };
+
+    let mut value = bytes as f64;
+    let mut exponent = 0;
+    while value >= base && exponent < suffixes.len() - 1 {
+        value /= base;
+        exponent += 1;
+    }
+
+    let number = format!("{:.1}", value);
+    format!("{} {}", number.trim_end_matches(".0"), suffixes[exponent])
+}
+
+impl FileEntry {
+    /// Build an entry from already-read metadata.
+    /// Missing metadata yields a zero size and no timestamps.
+    pub fn from_metadata(
+        path: &Path,
+        file_name: &OsStr,
+        file_type: FileType,
+        depth: usize,
+        metadata: Option<&Metadata>,
+        timestamps: TimestampFormat,
+        byte_unit: ByteUnit,
+    ) -> Self {
+        let size = metadata.map(|m| m.len()).unwrap_or(0);
+        let extension = file_extension(path);
+        let category = match &extension {
+            Some(ext) if !file_type.is_dir() => classify_extension(ext),
+            _ => FileCategory::Other,
+        };
+        let (modified, modified_ms) = timestamps.render(
+            metadata
+                .and_then(|m| m.modified().ok())
+                .map(DateTime::<Utc>::from),
+        );
+        let (created, created_ms) = timestamps.render(
+            metadata
+                .and_then(|m| m.created().ok())
+                .map(DateTime::<Utc>::from),
+        );
+        let (accessed, accessed_ms) = timestamps.render(
+            metadata
+                .and_then(|m| m.accessed().ok())
+                .map(DateTime::<Utc>::from),
+        );
+
+        Self {
+            id: String::new(),
+            name: file_name.to_string_lossy().to_string(),
+            path: path.to_string_lossy().to_string(),
+            size,
+            size_formatted: format_bytes(size, byte_unit),
+            is_directory: file_type.is_dir(),
+            is_file: file_type.is_file(),
+            is_symlink: file_type.is_symlink(),
+            extension,
+            modified,
+            created,
+            accessed,
+            modified_ms,
+            created_ms,
+            accessed_ms,
+            depth,
+            children_count: None,
+            hash: None,
+            mime_type: None,
+            category,
+            permissions: None,
+            permissions_string: None,
+            owner_uid: None,
+            owner_gid: None,
+            is_sparse: None,
+            is_broken: None,
+        }
+    }
+
+    /// Fill mode bits and ownership from already-read metadata
+    #[cfg(unix)]
+    pub fn read_permissions(&mut self, metadata: &Metadata) {
+        use std::os::unix::fs::MetadataExt;
+
+        let mode = metadata.mode() & 0o7777;
+        self.permissions = Some(mode);
+        self.permissions_string = Some(format_mode(mode));
+        self.owner_uid = Some(metadata.uid());
+        self.owner_gid = Some(metadata.gid());
+    }
+
+    /// Windows has no mode bits or numeric owners; the fields stay `None`
+    #[cfg(not(unix))]
+    pub fn read_permissions(&mut self, _metadata: &Metadata) {}
+
+    /// Compare allocated blocks against the logical size of a regular file
+    #[cfg(unix)]
+    pub fn read_sparseness(&mut self, metadata: &Metadata) {
+        use std::os::unix::fs::MetadataExt;
+
+        if metadata.is_file() {
+            // `blocks` is always in 512-byte units, whatever the filesystem block size
+            self.is_sparse = Some(metadata.blocks() * 512 < metadata.len());
+        }
+    }
+
+    /// Allocation sizes aren't exposed through std off Unix; `is_sparse` stays `None`
+    #[cfg(not(unix))]
+    pub fn read_sparseness(&mut self, _metadata: &Metadata) {}
+
+    /// Follow a symlink entry to see whether its target still exists.
+    /// Other entries are left alone.
+    pub fn check_link_target(&mut self, path: &Path) {
+        if self.is_symlink {
+            self.is_broken = Some(fs::metadata(path).is_err());
+        }
+    }
+
+    /// Stat a single path without following symlinks.
+    /// Directories get their immediate child count.
+    pub fn from_path(path: &Path, byte_unit: ByteUnit) -> io::Result<Self> {
+        let metadata = fs::symlink_metadata(path)?;
+        let file_name = path.file_name().unwrap_or(path.as_os_str());
+
+        let mut entry = Self::from_metadata(
+            path,
+            file_name,
+            metadata.file_type(),
+            0,
+            Some(&metadata),
+            TimestampFormat::default(),
+            byte_unit,
+        );
+
+        if metadata.is_dir() {
+            entry.children_count = Some(fs::read_dir(path)?.count() as u64);
+        }
+        entry.check_link_target(path);
+
+        Ok(entry)
+    }
+}
+
+/// Render mode bits as `ls` does, with `s`/`t` standing in for execute
+/// when setuid, setgid or sticky is set (`S`/`T` without execute)
+#[cfg(unix)]
+fn format_mode(mode: u32) -> String {
+    let special = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')];
+    let mut rendered = String::with_capacity(9);
+
+    for (class, (special_bit, special_char)) in special.into_iter().enumerate() {
+        let bits = (mode >> (6 - class * 3)) & 0o7;
+        rendered.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        rendered.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+
+        let execute = bits & 0o1 != 0;
+        rendered.push(match (mode & special_bit != 0, execute) {
+            (true, true) => special_char,
+            (true, false) => special_char.to_ascii_uppercase(),
+            (false, true) => 'x',
+            (false, false) => '-',
+        });
+    }
+
+    rendered
+}
+
+/// Path that could not be fully read during a scan
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanErrorEntry {
+    pub path: String,
+    pub message: String,
+    pub kind: String,
 }
 
 /// Directory scan result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScanResult {
     pub scan_id: String,
     pub root_path: String,
+    /// Per-root subtotals, in scan order
+    pub roots: Vec<RootSummary>,
     pub total_files: u64,
     pub total_directories: u64,
     pub total_size: u64,
     pub total_size_formatted: String,
     pub entries: Vec<FileEntry>,
+    pub errors: Vec<ScanErrorEntry>,
+    /// Per-extension breakdown, only filled when requested
+    pub extension_stats: Option<Vec<ExtensionStat>>,
+    /// File-age histogram, only filled when requested
+    pub age_buckets: Option<Vec<AgeBucket>>,
+    /// Entries counted in the totals per depth, shallowest first, with every
+    /// level down to the deepest one present. A resumed scan only covers what
+    /// it walked after resuming.
+    pub depth_distribution: Vec<DepthBucket>,
+    /// Per-category breakdown, only filled when requested
+    pub category_stats: Option<Vec<CategorySummary>>,
     pub duration_ms: u64,
     pub completed_at: String,
+    /// True when the scan stopped early at `maxEntries`
+    pub truncated: bool,
+    /// Entries skipped because their symlink target was already counted
+    pub symlink_duplicates_skipped: u64,
+    /// Directories not descended into because `skipSystemDirs` matched their name
+    pub pruned_directories: u64,
+    /// Algorithm behind `FileEntry.hash`; `None` when files weren't hashed
+    pub hash_algorithm: Option<HashAlgorithm>,
     pub status: ScanStatus,
+    /// Request that produced this result, kept so parts of it can be rescanned
+    #[serde(skip)]
+    pub request: Option<std::sync::Arc<ScanRequest>>,
 }
 
-/// Scan progress event payload
+/// Totals contributed by one root of a scan
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RootSummary {
+    pub path: String,
+    pub total_files: u64,
+    pub total_directories: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+/// Totals of a size-only scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeSummary {
+    pub total_files: u64,
+    pub total_directories: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+impl From<&ScanResult> for SizeSummary {
+    fn from(result: &ScanResult) -> Self {
+        Self {
+            total_files: result.total_files,
+            total_directories: result.total_directories,
+            total_size: result.total_size,
+            total_size_formatted: result.total_size_formatted.clone(),
+        }
+    }
+}
+
+/// Outcome of a scan streamed straight into an NDJSON file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NdjsonScanSummary {
+    pub scan_id: String,
+    pub output_path: String,
+    pub total_files: u64,
+    pub total_directories: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+    pub lines_written: u64,
+    pub bytes_written: u64,
+    pub duration_ms: u64,
+    pub status: ScanStatus,
+}
+
+/// Condensed record of a finished scan kept in the history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanSummary {
+    pub scan_id: String,
+    pub root_path: String,
+    pub total_files: u64,
+    pub total_directories: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+    pub duration_ms: u64,
+    pub completed_at: String,
+    pub status: ScanStatus,
+}
+
+impl From<&ScanResult> for ScanSummary {
+    fn from(result: &ScanResult) -> Self {
+        Self {
+            scan_id: result.scan_id.clone(),
+            root_path: result.root_path.clone(),
+            total_files: result.total_files,
+            total_directories: result.total_directories,
+            total_size: result.total_size,
+            total_size_formatted: result.total_size_formatted.clone(),
+            duration_ms: result.duration_ms,
+            completed_at: result.completed_at.clone(),
+            status: result.status.clone(),
+        }
+    }
+}
+
+/// Streamed batch of entries emitted during a scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanBatch {
+    pub scan_id: String,
+    pub batch_index: u64,
+    pub entries: Vec<FileEntry>,
+}
+
+/// How much per-entry metadata a scan reads
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataLevel {
+    /// Names and structure only: no stat calls, sizes are 0 and timestamps empty
+    None,
+    /// Sizes only; timestamps are left empty
+    Basic,
+    /// Sizes and timestamps, including access time
+    #[default]
+    Full,
+}
+
+/// Which kinds of entries a scan returns. Only affects what is collected;
+/// every directory is still walked and totals always cover the whole tree.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKindFilter {
+    #[default]
+    All,
+    /// Everything except directories, including symlinks that aren't followed
+    FilesOnly,
+    DirectoriesOnly,
+}
+
+impl EntryKindFilter {
+    pub fn keeps(self, is_directory: bool) -> bool {
+        match self {
+            Self::All => true,
+            Self::FilesOnly => !is_directory,
+            Self::DirectoriesOnly => is_directory,
+        }
+    }
+}
+
+/// Coarse stage of a running scan
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanPhase {
+    Counting,
+    Walking,
+    Hashing,
+    Aggregating,
+}
+
+/// Phase transition event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanPhaseEvent {
+    pub scan_id: String,
+    pub phase: ScanPhase,
+    /// Extra context such as the root being walked
+    pub detail: Option<String>,
+}
+
+/// Set of files sharing identical content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub hash: String,
+    pub size: u64,
+    pub size_formatted: String,
+    pub paths: Vec<String>,
+}
+
+/// Duplicate detection report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateReport {
+    pub groups: Vec<DuplicateGroup>,
+}
+
+/// One image of a `SimilarGroup`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarImage {
+    pub path: String,
+    pub size: u64,
+    /// Hamming distance between this image's perceptual hash and the group's
+    pub distance: u32,
+}
+
+/// Images that look alike, such as resized or re-encoded copies of one photo
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SimilarGroup {
+    /// Perceptual hash of the first image, which the others were compared with
+    pub hash: String,
+    /// Closest first, starting with the first image at distance 0
+    pub images: Vec<SimilarImage>,
+}
+
+/// Regular files whose modified time falls in one age range
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgeBucket {
+    /// `<1d`, `<7d`, `<30d`, `<1y`, `older`, or `unknown` when no timestamp is available
+    pub label: String,
+    pub file_count: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+/// Files and directories found at one depth below a scanned root
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthBucket {
+    /// 0 is the root itself
+    pub depth: usize,
+    pub file_count: u64,
+    pub dir_count: u64,
+}
+
+/// One problem found while validating a request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    /// Request field the issue belongs to, in camelCase
+    pub field: String,
+    pub message: String,
+}
+
+/// Outcome of a dry-run request check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    /// True when the scan would start; warnings don't affect this
+    pub valid: bool,
+    pub errors: Vec<ValidationIssue>,
+    pub warnings: Vec<ValidationIssue>,
+    /// Order-of-magnitude entry count from a shallow sample of each root
+    pub estimated_entries: Option<u64>,
+}
+
+/// How much of the tree an estimate actually looked at
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateConfidence {
+    /// The sample ran out of time before reading the top levels
+    Low,
+    /// The top levels were read; deeper directories were extrapolated
+    Medium,
+    /// The whole tree fit in the sample, so the counts are exact
+    High,
+}
+
+/// Predicted size and duration of a scan, from a time-boxed sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanEstimate {
+    pub estimated_files: u64,
+    pub estimated_directories: u64,
+    pub estimated_duration_ms: u64,
+    pub confidence: EstimateConfidence,
+    /// Entries actually listed while sampling
+    pub sampled_entries: u64,
+    pub sample_ms: u64,
+}
+
+/// Differences between two scans of the same tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanDiff {
+    /// Entries only present in the new scan
+    pub added: Vec<FileEntry>,
+    /// Paths only present in the base scan
+    pub removed: Vec<String>,
+    /// Files whose size or modified time changed, as they are now
+    pub modified: Vec<FileEntry>,
+    /// New total size minus base total size
+    pub size_delta: i64,
+}
+
+/// Entry field a query can sort by
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryField {
+    #[default]
+    Path,
+    Name,
+    Size,
+    Modified,
+}
+
+/// Filter, sort and page window over a stored scan's entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QueryParams {
+    pub offset: usize,
+    pub limit: usize,
+    pub sort_by: EntryField,
+    pub descending: bool,
+    /// Case-insensitive substring the entry name must contain
+    pub name_contains: Option<String>,
+}
+
+impl Default for QueryParams {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: 100,
+            sort_by: EntryField::Path,
+            descending: false,
+            name_contains: None,
+        }
+    }
+}
+
+/// One page of query results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryPage {
+    /// Retained scan the page was taken from, for requesting further pages
+    pub scan_id: String,
+    pub entries: Vec<FileEntry>,
+    /// Matches before paging, for sizing a virtual scroller
+    pub total_matched: u64,
+}
+
+/// One entry of a retained scan with its children nested below it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNode {
+    pub entry: FileEntry,
+    /// Directories first, then by name
+    pub children: Vec<TreeNode>,
+    /// Children left out because the node sits at the depth cap
+    pub omitted_children: u64,
+}
+
+/// Disk usage of all regular files sharing one extension
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionStat {
+    /// Lowercase extension without the dot; `None` groups files without one
+    pub extension: Option<String>,
+    pub file_count: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+/// Disk usage of all regular files in one category.
+/// Directories are never counted, so there is no directory row.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySummary {
+    pub category: FileCategory,
+    pub file_count: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+/// Kind of filesystem change reported by a watch
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FsEventKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// Filesystem change event payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FsEvent {
+    pub watch_id: String,
+    pub kind: FsEventKind,
+    pub path: String,
+    pub timestamp: String,
+}
+
+/// Digest algorithm used for file hashing
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Blake3,
+    Md5,
+}
+
+/// How `find_duplicates` narrows a tree down to same-size candidates
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupMode {
+    /// Collect every regular file and bucket them by size
+    #[default]
+    Exact,
+    /// Walk the tree twice. The first pass only records sizes, using a bloom
+    /// filter to spot sizes seen more than once; the second collects just the
+    /// files with such a size, so unique-size files are never held in memory.
+    ///
+    /// The filter can report a size as seen when it wasn't: about 1% of sizes
+    /// on trees of up to a million files, or of the `estimate_total` count,
+    /// and more beyond. Such a file is kept as a candidate and then dropped
+    /// before hashing because it has no twin, so the groups are exactly those
+    /// of `Exact` and only memory use goes up. The filter never misses a
+    /// repeated size, so no duplicate is lost. The price is the extra walk,
+    /// and a file whose size changes between the passes can be missed.
+    Approximate,
+}
+
+/// OS scheduling priority a scan runs at
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanPriority {
+    #[default]
+    Normal,
+    /// Walk on a dedicated thread at the lowest OS priority and yield more
+    /// often, for background indexing that shouldn't slow foreground work
+    Low,
+}
+
+/// Outcome of checking one manifest entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyStatus {
+    Match,
+    Mismatch,
+    Missing,
+    Unreadable,
+}
+
+/// One manifest line with its recomputed digest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyEntry {
+    pub path: String,
+    pub expected: String,
+    pub actual: Option<String>,
+    pub status: VerifyStatus,
+    pub error: Option<String>,
+}
+
+/// Checksum verification report, one entry per manifest line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub entries: Vec<VerifyEntry>,
+    pub matched: u64,
+    pub mismatched: u64,
+    pub missing: u64,
+    pub unreadable: u64,
+}
+
+/// Path that could not be moved to the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashFailure {
+    pub path: String,
+    pub message: String,
+}
+
+/// Per-path outcome of a trash request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashResult {
+    pub trashed: Vec<String>,
+    pub errors: Vec<TrashFailure>,
+}
+
+/// Outcome of a line-oriented export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportStats {
+    pub lines_written: u64,
+    /// Size of the output before compression
+    pub bytes_written: u64,
+    /// Size on disk when the output was gzip-compressed
+    pub compressed_bytes: Option<u64>,
+}
+
+/// Head of a file decoded for the inline viewer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextPreview {
+    /// Lossily decoded text; empty when `is_binary` is set
+    pub content: String,
+    /// True when the file continues past what was read
+    pub truncated: bool,
+    pub is_binary: bool,
+}
+
+/// Scan progress event payload
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ScanProgress {
     pub scan_id: String,
+    /// Increases by one with every emitted event, starting at 1; the final
+    /// event carries the highest value. Assigned by the emitter, not the sender.
+    /// Follow-up passes such as image hashing continue the scan's numbering.
+    pub seq: u64,
     pub current_path: String,
+    /// Root this update covers when roots are walked concurrently; `None`
+    /// for updates about the scan as a whole
+    pub root: Option<String>,
     pub files_scanned: u64,
     pub directories_scanned: u64,
     pub bytes_scanned: u64,
     pub bytes_scanned_formatted: String,
     pub progress_percent: f64,
     pub estimated_total: Option<u64>,
+    /// Entries per second over a short rolling window
+    pub files_per_sec: f64,
+    /// Only present when `estimated_total` is known
+    pub eta_ms: Option<u64>,
     pub elapsed_ms: u64,
     pub status: ScanStatus,
 }
 
 /// Scan status enum
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ScanStatus {
     Idle,
+    Counting,
     Scanning,
+    Paused,
     Completed,
+    /// Stopped early after hitting the entry cap; totals cover what was walked
+    Truncated,
     Cancelled,
+    /// Stopped because the app passed its configured memory limit
+    MemoryLimitExceeded,
     Error,
 }
 
@@ -98,9 +875,36 @@ pub struct SystemInfo {
     pub version: String,
     pub hostname: String,
     pub cpu_cores: usize,
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    /// Load across all cores since the previous call; 0 on the first call
+    pub cpu_usage_percent: f32,
     pub timestamp: String,
 }
 
+/// Mounted volume with capacity figures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeInfo {
+    pub name: String,
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub file_system: String,
+    pub is_removable: bool,
+}
+
+/// Capacity of the volume holding a given path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    /// Mount point of the matched volume
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub available_bytes: u64,
+}
+
 /// Heartbeat response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -110,16 +914,189 @@ pub struct HeartbeatResponse {
     pub active_scans: usize,
     pub timestamp: String,
     pub version: String,
+    /// Scans registered since startup
+    pub total_scans_started: u64,
+    /// Scans that ended without being cancelled, including failed ones
+    pub total_scans_completed: u64,
+    pub total_scans_cancelled: u64,
+    /// Most scans that were active at the same time
+    pub peak_active_scans: u64,
 }
 
-/// Scan request from frontend
+/// Severity of a captured log line
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            tracing::Level::ERROR => Self::Error,
+        }
+    }
+}
+
+/// One tracing event kept for the diagnostics panel
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    pub timestamp: String,
+    pub level: LogLevel,
+    /// Module that logged the event, e.g. `velox_core::scanner`
+    pub target: String,
+    pub message: String,
+}
+
+/// Scan request from frontend
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ScanRequest {
+    /// Single root; ignored when `paths` is non-empty
+    #[serde(default)]
     pub path: String,
+    /// Several roots combined into one result, scanned in order unless `concurrent_roots`
+    pub paths: Option<Vec<String>>,
+    /// Walk the roots in `paths` at the same time, up to `threads` at once
+    pub concurrent_roots: Option<bool>,
     pub max_depth: Option<usize>,
     pub include_hidden: bool,
     pub follow_symlinks: bool,
+    pub dedupe_symlink_targets: Option<bool>,
+    pub include_globs: Option<Vec<String>>,
+    pub exclude_globs: Option<Vec<String>>,
+    pub estimate_total: Option<bool>,
+    pub stream_batch_size: Option<usize>,
+    pub max_entries: Option<usize>,
+    pub entry_filter: Option<EntryKindFilter>,
+    pub metadata: Option<MetadataLevel>,
+    pub timestamp_format: Option<TimestampFormat>,
+    pub read_permissions: Option<bool>,
+    pub detect_sparse: Option<bool>,
+    pub report_broken_symlinks: Option<bool>,
+    pub only_empty_files: Option<bool>,
+    pub aggregate_dir_sizes: Option<bool>,
+    pub hash_files: Option<bool>,
+    pub hash_max_bytes: Option<u64>,
+    pub hash_algorithm: Option<HashAlgorithm>,
+    pub io_retries: Option<u8>,
+    pub dedup_memory_mode: Option<DedupMode>,
+    pub max_bytes_per_sec: Option<u64>,
+    pub detect_mime: Option<bool>,
+    pub parallel: Option<bool>,
+    pub threads: Option<usize>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub count_filtered: Option<bool>,
+    pub modified_after: Option<String>,
+    pub modified_before: Option<String>,
+    pub include_extensions: Option<Vec<String>>,
+    pub exclude_extensions: Option<Vec<String>>,
+    pub name_regex: Option<String>,
+    pub name_contains: Option<String>,
+    pub filter_directory_names: Option<bool>,
+    pub skip_system_dirs: Option<bool>,
+    pub skip_dir_names: Option<Vec<String>>,
+    pub respect_gitignore: Option<bool>,
+    /// Write resume checkpoints to this file while scanning
+    pub checkpoint_path: Option<String>,
+    /// Give every entry an `id`; off by default since nothing server-side needs one
+    pub generate_ids: Option<bool>,
+    pub priority: Option<ScanPriority>,
+}
+
+impl ScanRequest {
+    /// Roots to scan: `paths` when given, otherwise the single `path`
+    pub fn root_paths(&self) -> Vec<String> {
+        match &self.paths {
+            Some(paths) if !paths.is_empty() => paths.clone(),
+            _ => vec![self.path.clone()],
+        }
+    }
+
+    /// Roots joined for logs, sessions and `ScanResult.root_path`
+    pub fn root_label(&self) -> String {
+        self.root_paths().join(", ")
+    }
+
+    /// This request narrowed by a filter spec; fields set on the spec win
+    pub fn with_filter(self, filter: EntryFilterSpec) -> Self {
+        Self {
+            include_extensions: filter.include_extensions.or(self.include_extensions),
+            exclude_extensions: filter.exclude_extensions.or(self.exclude_extensions),
+            min_size: filter.min_size.or(self.min_size),
+            max_size: filter.max_size.or(self.max_size),
+            name_regex: filter.name_regex.or(self.name_regex),
+            name_contains: filter.name_contains.or(self.name_contains),
+            ..self
+        }
+    }
+}
+
+/// Predicate for `count_matching`, built from the same filters a scan request has
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryFilterSpec {
+    pub include_extensions: Option<Vec<String>>,
+    pub exclude_extensions: Option<Vec<String>>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub name_regex: Option<String>,
+    pub name_contains: Option<String>,
+}
+
+/// Entries matching a filter spec, counted without collecting them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchCount {
+    pub files: u64,
+    /// Directories walked; name filters only narrow these with `filterDirectoryNames`
+    pub directories: u64,
+    pub total_size: u64,
+    pub total_size_formatted: String,
+}
+
+impl From<&ScanResult> for MatchCount {
+    fn from(result: &ScanResult) -> Self {
+        Self {
+            files: result.total_files,
+            directories: result.total_directories,
+            total_size: result.total_size,
+            total_size_formatted: result.total_size_formatted.clone(),
+        }
+    }
+}
+
+/// A scan request saved under a name so it can be run again
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProfile {
+    /// Unique, ignoring case
+    pub name: String,
+    /// Settings and roots replayed by `run_profile`
+    pub request: ScanRequest,
+    pub saved_at: String,
+}
+
+/// Live view of an in-flight scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanSnapshot {
+    pub scan_id: String,
+    pub root_path: String,
+    pub status: ScanStatus,
+    pub started_at: String,
+    pub files_scanned: u64,
+    pub elapsed_ms: u64,
 }
 
 /// Active scan session
@@ -128,18 +1105,56 @@ pub struct ScanSession {
     pub id: ScanId,
     pub root_path: String,
     pub started_at: DateTime<Utc>,
-    pub status: ScanStatus,
     pub cancelled: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    pub paused: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Live counters updated by the scanner, readable while it runs
+    pub files_scanned: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    pub directories_scanned: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    pub bytes_scanned: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Unix millis of the last sign of life from the scanner
+    pub last_activity: std::sync::Arc<std::sync::atomic::AtomicI64>,
+    /// Last `seq` handed out to a progress update of this session
+    pub progress_seq: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Multi-root session this per-root session also counts into
+    pub parent: Option<std::sync::Arc<ScanSession>>,
 }
 
 impl ScanSession {
     pub fn new(root_path: String) -> Self {
+        let started_at = Utc::now();
         Self {
             id: ScanId::new(),
             root_path,
-            started_at: Utc::now(),
-            status: ScanStatus::Idle,
+            started_at,
             cancelled: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            paused: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            files_scanned: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            directories_scanned: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            bytes_scanned: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            last_activity: std::sync::Arc::new(std::sync::atomic::AtomicI64::new(
+                started_at.timestamp_millis(),
+            )),
+            progress_seq: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            parent: None,
+        }
+    }
+
+    /// Session for one root of a concurrent multi-root scan. Shares the id
+    /// and control flags with `parent`, so cancelling or pausing the parent
+    /// reaches every root, but keeps its own counters for the root's summary.
+    pub fn for_root(parent: &std::sync::Arc<ScanSession>, root_path: String) -> Self {
+        Self {
+            id: parent.id.clone(),
+            root_path,
+            started_at: Utc::now(),
+            cancelled: parent.cancelled.clone(),
+            paused: parent.paused.clone(),
+            files_scanned: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            directories_scanned: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            bytes_scanned: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            last_activity: parent.last_activity.clone(),
+            progress_seq: parent.progress_seq.clone(),
+            parent: Some(parent.clone()),
         }
     }
 
@@ -150,5 +1165,105 @@ impl ScanSession {
     pub fn cancel(&self) {
         self.cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
     }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn record_file(&self, size: u64) {
+        self.files_scanned.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.bytes_scanned.fetch_add(size, std::sync::atomic::Ordering::Relaxed);
+        if let Some(parent) = &self.parent {
+            parent.record_file(size);
+        }
+    }
+
+    pub fn record_directory(&self) {
+        self.directories_scanned.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if let Some(parent) = &self.parent {
+            parent.record_directory();
+        }
+    }
+
+    /// Start the counters from totals restored out of a checkpoint
+    pub fn restore_totals(&self, files: u64, directories: u64, bytes: u64) {
+        self.files_scanned.fetch_add(files, std::sync::atomic::Ordering::Relaxed);
+        self.directories_scanned.fetch_add(directories, std::sync::atomic::Ordering::Relaxed);
+        self.bytes_scanned.fetch_add(bytes, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Current (files, directories, bytes) totals
+    pub fn totals(&self) -> (u64, u64, u64) {
+        (
+            self.files_scanned.load(std::sync::atomic::Ordering::Relaxed),
+            self.directories_scanned.load(std::sync::atomic::Ordering::Relaxed),
+            self.bytes_scanned.load(std::sync::atomic::Ordering::Relaxed),
+        )
+    }
+
+    /// Mark the scan as still making progress
+    pub fn touch(&self) {
+        self.last_activity
+            .store(Utc::now().timestamp_millis(), std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Milliseconds since the scanner last called `touch`
+    pub fn idle_ms(&self, now: DateTime<Utc>) -> u64 {
+        let last = self.last_activity.load(std::sync::atomic::Ordering::Relaxed);
+        (now.timestamp_millis() - last).max(0) as u64
+    }
+
+    /// Status derived from the control flags
+    pub fn current_status(&self) -> ScanStatus {
+        if self.is_cancelled() {
+            ScanStatus::Cancelled
+        } else if self.is_paused() {
+            ScanStatus::Paused
+        } else {
+            ScanStatus::Scanning
+        }
+    }
+
+    fn elapsed_ms(&self, now: DateTime<Utc>) -> u64 {
+        now.signed_duration_since(self.started_at).num_milliseconds().max(0) as u64
+    }
+
+    /// Point-in-time view of a running scan; only reads atomics
+    pub fn snapshot(&self) -> ScanSnapshot {
+        ScanSnapshot {
+            scan_id: self.id.to_string(),
+            root_path: self.root_path.clone(),
+            status: self.current_status(),
+            started_at: self.started_at.to_rfc3339(),
+            files_scanned: self.files_scanned.load(std::sync::atomic::Ordering::Relaxed),
+            elapsed_ms: self.elapsed_ms(Utc::now()),
+        }
+    }
+
+    /// Summary for a scan that was cancelled before producing a result.
+    /// Totals reflect what was counted up to the cancellation.
+    pub fn cancelled_summary(&self, byte_unit: ByteUnit) -> ScanSummary {
+        let now = Utc::now();
+        let (total_files, total_directories, total_size) = self.totals();
+        ScanSummary {
+            scan_id: self.id.to_string(),
+            root_path: self.root_path.clone(),
+            total_files,
+            total_directories,
+            total_size,
+            total_size_formatted: format_bytes(total_size, byte_unit),
+            duration_ms: self.elapsed_ms(now),
+            completed_at: now.to_rfc3339(),
+            status: ScanStatus::Cancelled,
+        }
+    }
 }
 
@@ -0,0 +1,195 @@
+// VELOX CORE - Request Validation
+// Dry-run checks for scan requests, without starting a scan
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::VeloxError;
+use crate::scanner::{
+    build_globset, build_regex, check_metadata_level, parse_timestamp, validate_root,
+};
+use crate::types::{MetadataLevel, ScanRequest, ValidationIssue, ValidationReport};
+
+/// Directories read per root when sampling for the entry estimate
+const SAMPLE_DIRS: usize = 64;
+
+impl ValidationReport {
+    fn error(&mut self, field: &str, error: VeloxError) {
+        self.errors.push(ValidationIssue {
+            field: field.to_string(),
+            message: error.to_string(),
+        });
+    }
+
+    fn warn(&mut self, field: &str, message: &str) {
+        self.warnings.push(ValidationIssue {
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+/// Rough entry count for one root. Reads up to `SAMPLE_DIRS` directories
+/// breadth-first, then assumes every directory still queued holds as many
+/// entries as the average one read. Exact for small trees, order-of-magnitude
+/// for large ones.
+fn estimate_entries(root: &Path) -> std::io::Result<u64> {
+    let mut queue: VecDeque<PathBuf> = VecDeque::new();
+    let mut entries: u64 = 0;
+    let mut dirs_read: usize = 0;
+
+    // The root itself must be readable; unreadable subdirectories are just skipped
+    let mut listing = Some(fs::read_dir(root)?);
+    while let Some(dir) = listing.take() {
+        dirs_read += 1;
+        for entry in dir.flatten() {
+            entries += 1;
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                queue.push_back(entry.path());
+            }
+        }
+
+        while dirs_read < SAMPLE_DIRS && listing.is_none() {
+            match queue.pop_front() {
+                Some(path) => listing = fs::read_dir(path).ok(),
+                None => break,
+            }
+        }
+    }
+
+    let average = entries as f64 / dirs_read as f64;
+    Ok(entries + (average * queue.len() as f64) as u64)
+}
+
+/// Check everything a scan would reject up front, plus settings that are
+/// valid but probably not what the caller meant
+pub fn validate_request(request: &ScanRequest) -> ValidationReport {
+    let mut report = ValidationReport {
+        valid: false,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        estimated_entries: None,
+    };
+
+    let mut estimated: u64 = 0;
+    let mut estimate_known = true;
+    for root in request.root_paths() {
+        if let Err(e) = validate_root(&root) {
+            report.error("path", e);
+            estimate_known = false;
+            continue;
+        }
+
+        match estimate_entries(Path::new(&root)) {
+            Ok(count) => estimated += count,
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                report.error("path", VeloxError::AccessDenied(root.clone()));
+                estimate_known = false;
+            }
+            Err(e) => {
+                report.error("path", VeloxError::io_at(&root, e));
+                estimate_known = false;
+            }
+        }
+    }
+    if estimate_known {
+        report.estimated_entries = Some(estimated);
+    }
+
+    check_patterns(request, &mut report);
+    check_filters(request, &mut report);
+    check_combinations(request, &mut report);
+
+    report.valid = report.errors.is_empty();
+    report
+}
+
+/// Globs and the name regex must compile
+fn check_patterns(request: &ScanRequest, report: &mut ValidationReport) {
+    if let Err(e) = build_globset(request.include_globs.as_deref().unwrap_or_default()) {
+        report.error("includeGlobs", e);
+    }
+    if let Err(e) = build_globset(request.exclude_globs.as_deref().unwrap_or_default()) {
+        report.error("excludeGlobs", e);
+    }
+    if let Err(e) = build_regex(request.name_regex.as_deref()) {
+        report.error("nameRegex", e);
+    }
+}
+
+/// Timestamps must parse, and ranges must not be empty
+fn check_filters(request: &ScanRequest, report: &mut ValidationReport) {
+    let after = parse_timestamp("modifiedAfter", request.modified_after.as_deref());
+    let before = parse_timestamp("modifiedBefore", request.modified_before.as_deref());
+
+    match (after, before) {
+        (Ok(Some(after)), Ok(Some(before))) if after > before => report.error(
+            "modifiedAfter",
+            VeloxError::InvalidArgument("modifiedAfter is later than modifiedBefore".into()),
+        ),
+        (after, before) => {
+            if let Err(e) = after {
+                report.error("modifiedAfter", e);
+            }
+            if let Err(e) = before {
+                report.error("modifiedBefore", e);
+            }
+        }
+    }
+
+    if let (Some(min), Some(max)) = (request.min_size, request.max_size) {
+        if min > max {
+            report.error(
+                "minSize",
+                VeloxError::InvalidArgument(format!("minSize {} is above maxSize {}", min, max)),
+            );
+        }
+    }
+
+    if let Err(e) = check_metadata_level(request, request.metadata.unwrap_or_default()) {
+        report.error("metadata", e);
+    }
+}
+
+/// Settings that are valid but have no effect as combined
+fn check_combinations(request: &ScanRequest, report: &mut ValidationReport) {
+    let enabled = |flag: Option<bool>| flag.unwrap_or(false);
+
+    if enabled(request.respect_gitignore) && enabled(request.parallel) {
+        report.warn(
+            "parallel",
+            "ignored because respectGitignore takes precedence",
+        );
+    }
+    if enabled(request.dedupe_symlink_targets) && !request.follow_symlinks {
+        report.warn(
+            "dedupeSymlinkTargets",
+            "has no effect unless followSymlinks is set",
+        );
+    }
+    if enabled(request.read_permissions) && request.metadata == Some(MetadataLevel::None) {
+        report.warn("readPermissions", "has no effect with metadata level none");
+    }
+    if enabled(request.detect_sparse) && request.metadata == Some(MetadataLevel::None) {
+        report.warn("detectSparse", "has no effect with metadata level none");
+    }
+    if enabled(request.report_broken_symlinks) && request.metadata == Some(MetadataLevel::None) {
+        report.warn("reportBrokenSymlinks", "has no effect with metadata level none");
+    }
+    if request.hash_max_bytes.is_some() && !enabled(request.hash_files) {
+        report.warn("hashMaxBytes", "has no effect unless hashFiles is set");
+    }
+    if request.skip_dir_names.is_some() && !enabled(request.skip_system_dirs) {
+        report.warn("skipDirNames", "has no effect unless skipSystemDirs is set");
+    }
+    if enabled(request.filter_directory_names)
+        && request.name_regex.is_none()
+        && request.name_contains.is_none()
+    {
+        report.warn(
+            "filterDirectoryNames",
+            "has no effect without nameRegex or nameContains",
+        );
+    }
+}
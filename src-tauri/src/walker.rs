@@ -0,0 +1,798 @@
+// VELOX CORE - Traversal Backends
+// Sequential (walkdir), parallel (jwalk), and gitignore-aware (ignore) walks behind one item type
+
+use std::collections::HashSet;
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, FileType, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use globset::GlobSet;
+use jwalk::{ClientState, Parallelism, WalkDirGeneric};
+use parking_lot::Mutex;
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::classify::file_extension;
+use crate::error::VeloxError;
+use crate::types::{ByteUnit, FileEntry, ScanErrorEntry, ScanSession, TimestampFormat};
+
+/// Pruning rules applied while walking, shared by every backend
+#[derive(Debug, Clone)]
+pub struct WalkFilter {
+    pub root: PathBuf,
+    pub include_hidden: bool,
+    pub include_set: Option<GlobSet>,
+    pub exclude_set: Option<GlobSet>,
+    /// Lowercase extensions without a leading dot
+    pub include_extensions: Option<Vec<String>>,
+    /// Lowercase extensions without a leading dot
+    pub exclude_extensions: Option<Vec<String>>,
+    /// Entry names must match this pattern
+    pub name_regex: Option<Regex>,
+    /// Lowercase substring entry names must contain
+    pub name_contains: Option<String>,
+    /// Apply the name rules to directories too, pruning those that don't match
+    pub filter_directory_names: bool,
+    /// Top-level entries already walked by an earlier, interrupted scan
+    pub skip_subtrees: Option<Arc<HashSet<PathBuf>>>,
+    /// Only walk this directory and the directories leading down to it
+    pub subtree: Option<PathBuf>,
+    /// Lowercase names of directories that are never descended into
+    pub skip_dir_names: Option<Arc<HashSet<String>>>,
+    /// Directories rejected by `skip_dir_names`, shared by every filter of a scan
+    pub pruned_dirs: Arc<AtomicU64>,
+}
+
+impl WalkFilter {
+    /// Whether an entry should be yielded. Rejected directories are not descended into.
+    pub fn allows(&self, path: &Path, file_name: &OsStr, depth: usize, is_dir: bool) -> bool {
+        // The scan root is always walked, even when it is itself a dotfile
+        // directory like `~/.config`
+        if depth == 0 {
+            return true;
+        }
+
+        if depth == 1 && self.skip_subtrees.as_ref().is_some_and(|skip| skip.contains(path)) {
+            return false;
+        }
+
+        if let Some(subtree) = &self.subtree {
+            if !path.starts_with(subtree) && !subtree.starts_with(path) {
+                return false;
+            }
+        }
+
+        if !self.include_hidden
+            && file_name
+                .to_str()
+                .map(|s| s.starts_with('.'))
+                .unwrap_or(false)
+        {
+            return false;
+        }
+
+        if is_dir && self.is_skipped_dir(file_name) {
+            self.pruned_dirs.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+
+        // Prune excluded entries so we never descend into them
+        if let Some(set) = &self.exclude_set {
+            if matches_globset(set, relative, is_dir) {
+                return false;
+            }
+        }
+
+        if (!is_dir || self.filter_directory_names) && !self.allows_name(file_name) {
+            return false;
+        }
+
+        // Include rules only narrow down files; directories pass through
+        // so the walk can still reach matching descendants
+        if is_dir {
+            return true;
+        }
+
+        if let Some(set) = &self.include_set {
+            if !set.is_match(relative) {
+                return false;
+            }
+        }
+
+        self.allows_extension(path)
+    }
+
+    /// Whether a directory is pruned by name under `skip_system_dirs`
+    fn is_skipped_dir(&self, file_name: &OsStr) -> bool {
+        self.skip_dir_names
+            .as_ref()
+            .is_some_and(|names| names.contains(&file_name.to_string_lossy().to_lowercase()))
+    }
+
+    /// Apply the name regex and case-insensitive substring, when set
+    fn allows_name(&self, file_name: &OsStr) -> bool {
+        if self.name_regex.is_none() && self.name_contains.is_none() {
+            return true;
+        }
+
+        let name = file_name.to_string_lossy();
+
+        if let Some(regex) = &self.name_regex {
+            if !regex.is_match(&name) {
+                return false;
+            }
+        }
+
+        match &self.name_contains {
+            Some(needle) => name.to_lowercase().contains(needle.as_str()),
+            None => true,
+        }
+    }
+
+    /// Apply the extension lists; exclusion wins over inclusion. A compound
+    /// extension matches in full or by its last part, so `gz` and `tar.gz`
+    /// both match `archive.tar.gz`.
+    fn allows_extension(&self, path: &Path) -> bool {
+        if self.include_extensions.is_none() && self.exclude_extensions.is_none() {
+            return true;
+        }
+
+        let mut extensions: Vec<String> = file_extension(path)
+            .into_iter()
+            .chain(path.extension().map(|e| e.to_string_lossy().to_string()))
+            .filter(|e| !e.is_empty())
+            .map(|e| e.to_lowercase())
+            .collect();
+        extensions.dedup();
+        let listed = |list: &[String]| extensions.iter().any(|ext| list.contains(ext));
+
+        if let Some(excluded) = &self.exclude_extensions {
+            if listed(excluded) {
+                return false;
+            }
+        }
+
+        match &self.include_extensions {
+            Some(included) => listed(included),
+            None => true,
+        }
+    }
+}
+
+/// Normalize user-supplied extensions to lowercase without a leading dot
+pub fn normalize_extensions(extensions: &[String]) -> Vec<String> {
+    extensions
+        .iter()
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
+}
+
+/// Match a path (relative to the scan root) against a glob set.
+/// Directories are also tested with a trailing separator so that
+/// patterns like `node_modules/**` prune the directory itself.
+fn matches_globset(set: &GlobSet, relative: &Path, is_dir: bool) -> bool {
+    set.is_match(relative) || (is_dir && set.is_match(relative.join("")))
+}
+
+/// Entry produced by any traversal backend
+#[derive(Debug)]
+pub struct WalkedEntry {
+    pub path: PathBuf,
+    pub file_name: OsString,
+    pub file_type: FileType,
+    pub depth: usize,
+    /// `None` when the walk was started without metadata reads
+    pub metadata: Option<io::Result<Metadata>>,
+}
+
+impl WalkedEntry {
+    /// Map the walked entry to a `FileEntry`.
+    /// Metadata that was not read or failed to load yields a zero size and no timestamps.
+    pub fn to_file_entry(&self, timestamps: TimestampFormat, byte_unit: ByteUnit) -> FileEntry {
+        let metadata = self.metadata.as_ref().and_then(|m| m.as_ref().ok());
+        FileEntry::from_metadata(
+            &self.path,
+            &self.file_name,
+            self.file_type,
+            self.depth,
+            metadata,
+            timestamps,
+            byte_unit,
+        )
+    }
+}
+
+/// Single item yielded by a walk
+#[derive(Debug)]
+pub enum WalkItem {
+    Entry(WalkedEntry),
+    Error(ScanErrorEntry),
+}
+
+pub type WalkIter = Box<dyn Iterator<Item = WalkItem> + Send>;
+
+/// Traversal options independent of the backend
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    pub max_depth: usize,
+    pub follow_symlinks: bool,
+    pub with_metadata: bool,
+}
+
+/// Read metadata the same way the walkers resolve file types
+fn read_metadata(path: &Path, follow_symlinks: bool) -> io::Result<Metadata> {
+    if follow_symlinks {
+        fs::metadata(path)
+    } else {
+        fs::symlink_metadata(path)
+    }
+}
+
+/// Error kind for paths that are genuinely inaccessible
+pub const ACCESS_DENIED_KIND: &str = "access_denied";
+
+/// Whether an IO error means the path can't be read with the current permissions
+pub fn is_access_denied(error: &io::Error) -> bool {
+    // ERROR_ACCESS_DENIED; std usually maps it already, but not for every API
+    #[cfg(windows)]
+    if error.raw_os_error() == Some(5) {
+        return true;
+    }
+
+    error.kind() == io::ErrorKind::PermissionDenied
+}
+
+/// Classify an IO error into the `kind` reported to the frontend
+pub fn io_error_kind(error: &io::Error) -> &'static str {
+    if is_access_denied(error) {
+        return ACCESS_DENIED_KIND;
+    }
+
+    match error.kind() {
+        io::ErrorKind::NotFound => "not_found",
+        _ => "io",
+    }
+}
+
+/// Message for an IO failure, using the `AccessDenied` wording for permission errors
+fn io_error_message(path: &Path, error: &io::Error, fallback: String) -> String {
+    if is_access_denied(error) {
+        VeloxError::AccessDenied(path.to_string_lossy().to_string()).to_string()
+    } else {
+        fallback
+    }
+}
+
+/// Record an IO failure for a specific path
+pub fn io_error_entry(path: &Path, error: &io::Error) -> ScanErrorEntry {
+    ScanErrorEntry {
+        path: path.to_string_lossy().to_string(),
+        message: io_error_message(path, error, error.to_string()),
+        kind: io_error_kind(error).to_string(),
+    }
+}
+
+/// Shared shape of walkdir and jwalk errors
+fn traversal_error_entry(
+    path: Option<&Path>,
+    fallback: &Path,
+    io_error: Option<&io::Error>,
+    is_loop: bool,
+    message: String,
+) -> ScanErrorEntry {
+    let path = path.unwrap_or(fallback);
+
+    let (kind, message) = match io_error {
+        _ if is_loop => ("symlink_cycle", message),
+        Some(e) => (io_error_kind(e), io_error_message(path, e, message)),
+        None => ("io", message),
+    };
+
+    ScanErrorEntry {
+        path: path.to_string_lossy().to_string(),
+        message,
+        kind: kind.to_string(),
+    }
+}
+
+/// Identity of a directory independent of the path used to reach it
+#[cfg(unix)]
+type DirKey = (u64, u64);
+#[cfg(not(unix))]
+type DirKey = PathBuf;
+
+/// Resolve the identity of a directory, reusing already-read metadata when possible
+#[cfg(unix)]
+fn dir_key(path: &Path, metadata: Option<&Metadata>) -> Option<DirKey> {
+    use std::os::unix::fs::MetadataExt;
+
+    match metadata {
+        Some(m) => Some((m.dev(), m.ino())),
+        None => fs::metadata(path).ok().map(|m| (m.dev(), m.ino())),
+    }
+}
+
+#[cfg(not(unix))]
+fn dir_key(path: &Path, _metadata: Option<&Metadata>) -> Option<DirKey> {
+    fs::canonicalize(path).ok()
+}
+
+/// Record a directory that was reached a second time through a symlink
+fn cycle_error_entry(path: &Path) -> ScanErrorEntry {
+    ScanErrorEntry {
+        path: path.to_string_lossy().to_string(),
+        message: "Directory already visited through another path".to_string(),
+        kind: "symlink_cycle".to_string(),
+    }
+}
+
+/// Sequential walker state.
+/// Directory identities are only tracked when following symlinks, since
+/// cycles and double visits are impossible otherwise. The set lives for a
+/// single walk and holds one small key per directory.
+struct SequentialWalk {
+    it: walkdir::IntoIter,
+    filter: WalkFilter,
+    options: WalkOptions,
+    visited: Option<HashSet<DirKey>>,
+}
+
+impl Iterator for SequentialWalk {
+    type Item = WalkItem;
+
+    fn next(&mut self) -> Option<WalkItem> {
+        loop {
+            let entry = match self.it.next()? {
+                Ok(entry) => entry,
+                Err(e) => {
+                    return Some(WalkItem::Error(traversal_error_entry(
+                        e.path(),
+                        &self.filter.root,
+                        e.io_error(),
+                        e.loop_ancestor().is_some(),
+                        e.to_string(),
+                    )))
+                }
+            };
+
+            let is_dir = entry.file_type().is_dir();
+            if !self.filter.allows(entry.path(), entry.file_name(), entry.depth(), is_dir) {
+                if is_dir {
+                    self.it.skip_current_dir();
+                }
+                continue;
+            }
+
+            let metadata = self
+                .options
+                .with_metadata
+                .then(|| read_metadata(entry.path(), self.options.follow_symlinks));
+
+            if let (true, Some(visited)) = (is_dir, &mut self.visited) {
+                let known = metadata.as_ref().and_then(|m| m.as_ref().ok());
+                if let Some(key) = dir_key(entry.path(), known) {
+                    if !visited.insert(key) {
+                        self.it.skip_current_dir();
+                        return Some(WalkItem::Error(cycle_error_entry(entry.path())));
+                    }
+                }
+            }
+
+            return Some(WalkItem::Entry(WalkedEntry {
+                metadata,
+                path: entry.path().to_path_buf(),
+                file_name: entry.file_name().to_os_string(),
+                file_type: entry.file_type(),
+                depth: entry.depth(),
+            }));
+        }
+    }
+}
+
+/// Single-threaded walk using `walkdir`
+pub fn walk_sequential(filter: WalkFilter, options: WalkOptions) -> WalkIter {
+    let it = WalkDir::new(&filter.root)
+        .max_depth(options.max_depth)
+        .follow_links(options.follow_symlinks)
+        .into_iter();
+
+    Box::new(SequentialWalk {
+        it,
+        visited: options.follow_symlinks.then(HashSet::new),
+        filter,
+        options,
+    })
+}
+
+/// Walker that honors `.gitignore` files, yielding already-filtered entries
+/// plus any directories skipped as double visits
+struct GitignoreWalk {
+    it: ignore::Walk,
+    root: PathBuf,
+    options: WalkOptions,
+    skipped: Arc<Mutex<Vec<ScanErrorEntry>>>,
+}
+
+impl Iterator for GitignoreWalk {
+    type Item = WalkItem;
+
+    fn next(&mut self) -> Option<WalkItem> {
+        // Cycles found by the entry filter are reported before moving on
+        if let Some(error) = self.skipped.lock().pop() {
+            return Some(WalkItem::Error(error));
+        }
+
+        let item = match self.it.next()? {
+            Ok(entry) => match entry.file_type() {
+                Some(file_type) => WalkItem::Entry(WalkedEntry {
+                    metadata: self
+                        .options
+                        .with_metadata
+                        .then(|| read_metadata(entry.path(), self.options.follow_symlinks)),
+                    path: entry.path().to_path_buf(),
+                    file_name: entry.file_name().to_os_string(),
+                    file_type,
+                    depth: entry.depth(),
+                }),
+                // Only stdin entries lack a file type, which a directory walk never yields
+                None => return self.next(),
+            },
+            Err(e) => WalkItem::Error(traversal_error_entry(
+                ignore_error_path(&e),
+                &self.root,
+                e.io_error(),
+                matches!(innermost_ignore_error(&e), ignore::Error::Loop { .. }),
+                e.to_string(),
+            )),
+        };
+
+        Some(item)
+    }
+}
+
+/// Unwrap the path and depth annotations around an `ignore` error
+fn innermost_ignore_error(error: &ignore::Error) -> &ignore::Error {
+    match error {
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => innermost_ignore_error(err),
+        other => other,
+    }
+}
+
+/// Path an `ignore` error refers to, if it carries one
+fn ignore_error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            ignore_error_path(err)
+        }
+        ignore::Error::Loop { child, .. } => Some(child),
+        _ => None,
+    }
+}
+
+/// Single-threaded walk using the `ignore` crate.
+/// Honors `.gitignore` (including nested ones), `.ignore`, `.git/info/exclude`,
+/// and the global git excludes file. Rules apply even outside a git repository.
+pub fn walk_gitignore(filter: WalkFilter, options: WalkOptions) -> WalkIter {
+    let root = filter.root.clone();
+    let skipped: Arc<Mutex<Vec<ScanErrorEntry>>> = Arc::default();
+    let visited: Option<Mutex<HashSet<DirKey>>> = options.follow_symlinks.then(Mutex::default);
+
+    let filter_skipped = skipped.clone();
+    let it = ignore::WalkBuilder::new(&root)
+        .max_depth(Some(options.max_depth))
+        .follow_links(options.follow_symlinks)
+        // Hidden entries are handled by `WalkFilter` so the root rule stays the same
+        .hidden(false)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .ignore(true)
+        .parents(true)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if !filter.allows(entry.path(), entry.file_name(), entry.depth(), is_dir) {
+                return false;
+            }
+
+            if let (true, Some(visited)) = (is_dir, &visited) {
+                if let Some(key) = dir_key(entry.path(), None) {
+                    if !visited.lock().insert(key) {
+                        filter_skipped.lock().push(cycle_error_entry(entry.path()));
+                        return false;
+                    }
+                }
+            }
+
+            true
+        })
+        .build();
+
+    Box::new(GitignoreWalk {
+        it,
+        root,
+        options,
+        skipped,
+    })
+}
+
+/// Per-entry state for jwalk, filled in on the worker threads
+#[derive(Debug, Default)]
+struct ParallelEntryState {
+    metadata: Option<io::Result<Metadata>>,
+    /// Directory was already visited through another path
+    cycle: bool,
+}
+
+/// Client state for jwalk
+#[derive(Debug, Default)]
+struct ParallelState;
+
+impl ClientState for ParallelState {
+    type ReadDirState = ();
+    type DirEntryState = ParallelEntryState;
+}
+
+/// Multi-threaded walk using `jwalk`.
+/// Directory reads, filtering, and metadata reads run on a dedicated rayon pool
+/// while entries are still yielded in walk order.
+pub fn walk_parallel(
+    filter: WalkFilter,
+    options: WalkOptions,
+    threads: usize,
+    session: Arc<ScanSession>,
+) -> WalkIter {
+    let root = filter.root.clone();
+    let follow_symlinks = options.follow_symlinks;
+    let with_metadata = options.with_metadata;
+    let visited: Option<Mutex<HashSet<DirKey>>> = follow_symlinks.then(Mutex::default);
+
+    let iter = WalkDirGeneric::<ParallelState>::new(&root)
+        .max_depth(options.max_depth)
+        .follow_links(follow_symlinks)
+        .skip_hidden(false)
+        .parallelism(Parallelism::RayonNewPool(threads.max(1)))
+        // jwalk also hands the root to this callback, as the only child of a
+        // parentless read, so it is stat'ed and marked visited like any other
+        .process_read_dir(move |_, _, _, children| {
+            // Stop fanning out new work once the scan is cancelled
+            if session.is_cancelled() {
+                children.clear();
+                return;
+            }
+
+            children.retain(|child| match child {
+                Ok(e) => filter.allows(&e.path(), &e.file_name, e.depth, e.file_type.is_dir()),
+                Err(_) => true,
+            });
+
+            for child in children.iter_mut().flatten() {
+                if with_metadata {
+                    child.client_state.metadata = Some(read_metadata(&child.path(), follow_symlinks));
+                }
+
+                if let (true, Some(visited)) = (child.file_type.is_dir(), &visited) {
+                    let known = child.client_state.metadata.as_ref().and_then(|m| m.as_ref().ok());
+                    if let Some(key) = dir_key(&child.path(), known) {
+                        if !visited.lock().insert(key) {
+                            child.client_state.cycle = true;
+                            child.read_children_path = None;
+                        }
+                    }
+                }
+            }
+        })
+        .into_iter()
+        .map(move |result| match result {
+            Ok(entry) if entry.client_state.cycle => {
+                WalkItem::Error(cycle_error_entry(&entry.path()))
+            }
+            Ok(entry) => WalkItem::Entry(WalkedEntry {
+                path: entry.path(),
+                file_name: entry.file_name.clone(),
+                file_type: entry.file_type,
+                depth: entry.depth,
+                metadata: entry.client_state.metadata,
+            }),
+            Err(e) => WalkItem::Error(traversal_error_entry(
+                e.path(),
+                &root,
+                e.io_error(),
+                e.loop_ancestor().is_some(),
+                e.to_string(),
+            )),
+        });
+
+    Box::new(iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FileCategory;
+
+    fn filter(root: &str) -> WalkFilter {
+        WalkFilter {
+            root: PathBuf::from(root),
+            include_hidden: false,
+            include_set: None,
+            exclude_set: None,
+            include_extensions: None,
+            exclude_extensions: None,
+            name_regex: None,
+            name_contains: None,
+            filter_directory_names: false,
+            skip_subtrees: None,
+            subtree: None,
+            skip_dir_names: None,
+            pruned_dirs: Arc::default(),
+        }
+    }
+
+    fn allows(filter: &WalkFilter, path: &str, depth: usize, is_dir: bool) -> bool {
+        let path = Path::new(path);
+        filter.allows(path, path.file_name().unwrap(), depth, is_dir)
+    }
+
+    #[test]
+    fn hidden_root_is_walked_but_hidden_children_are_not() {
+        let filter = filter("/home/user/.config");
+
+        assert!(allows(&filter, "/home/user/.config", 0, true));
+        assert!(allows(&filter, "/home/user/.config/app", 1, true));
+        assert!(!allows(&filter, "/home/user/.config/.cache", 1, true));
+        assert!(!allows(&filter, "/home/user/.config/app/.env", 2, false));
+    }
+
+    #[test]
+    fn skipped_dir_names_prune_directories_only() {
+        let mut filter = filter("/src");
+        filter.skip_dir_names = Some(Arc::new(HashSet::from(["node_modules".to_string()])));
+
+        assert!(!allows(&filter, "/src/Node_Modules", 1, true));
+        assert!(allows(&filter, "/src/node_modules", 1, false));
+        assert!(allows(&filter, "/src/lib", 1, true));
+        assert_eq!(filter.pruned_dirs.load(Ordering::Relaxed), 1);
+
+        // The root is walked even when its own name is on the list
+        assert!(allows(&filter, "/node_modules", 0, true));
+    }
+
+    #[test]
+    fn name_filters_apply_to_files_and_optionally_directories() {
+        let mut filter = filter("/src");
+        filter.name_regex = Some(Regex::new(r"^test_.*\.rs$").unwrap());
+        filter.name_contains = Some("walk".to_string());
+
+        assert!(allows(&filter, "/src/test_Walker.rs", 1, false));
+        assert!(!allows(&filter, "/src/test_scanner.rs", 1, false));
+        assert!(!allows(&filter, "/src/walker.rs", 1, false));
+
+        // Directories pass so matching files below them are still reached
+        assert!(allows(&filter, "/src/nested", 1, true));
+        filter.filter_directory_names = true;
+        assert!(!allows(&filter, "/src/nested", 1, true));
+        assert!(allows(&filter, "/src/test_walk.rs", 1, true));
+    }
+
+    #[test]
+    fn name_regex_matches_the_file_name() {
+        let mut filter = filter("/var");
+        filter.name_regex = Some(Regex::new(r".*\.log$").unwrap());
+
+        assert!(allows(&filter, "/var/app.log", 1, false));
+        assert!(!allows(&filter, "/var/app.txt", 1, false));
+    }
+
+    #[test]
+    fn name_contains_ignores_case() {
+        // The scanner lowercases the needle when it builds the filter
+        let mut filter = filter("/docs");
+        filter.name_contains = Some("report".to_string());
+
+        assert!(allows(&filter, "/docs/Q3_REPORT.pdf", 1, false));
+        assert!(!allows(&filter, "/docs/notes.txt", 1, false));
+    }
+
+    fn walked(path: &Path, metadata: Option<io::Result<Metadata>>) -> WalkedEntry {
+        WalkedEntry {
+            path: path.to_path_buf(),
+            file_name: path.file_name().unwrap().to_os_string(),
+            file_type: fs::symlink_metadata(path).unwrap().file_type(),
+            depth: 1,
+            metadata,
+        }
+    }
+
+    #[test]
+    fn walked_entries_map_names_extensions_and_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("README");
+        let archive = dir.path().join("backup.tar.gz");
+        let sub = dir.path().join("photos.d");
+        fs::write(&plain, "hello").unwrap();
+        fs::write(&archive, "").unwrap();
+        fs::create_dir(&sub).unwrap();
+
+        let entry = walked(&plain, Some(fs::metadata(&plain)))
+            .to_file_entry(TimestampFormat::default(), ByteUnit::Binary);
+        assert_eq!(entry.name, "README");
+        assert_eq!(entry.extension, None);
+        assert_eq!(entry.size, 5);
+        assert!(entry.is_file && !entry.is_directory);
+        assert!(entry.modified.is_some());
+
+        let entry = walked(&archive, Some(fs::metadata(&archive)))
+            .to_file_entry(TimestampFormat::default(), ByteUnit::Binary);
+        assert_eq!(entry.extension.as_deref(), Some("tar.gz"));
+        assert_eq!(entry.category, FileCategory::Archive);
+
+        // Directories keep a dotted name's extension but are never categorized
+        let entry = walked(&sub, Some(fs::metadata(&sub)))
+            .to_file_entry(TimestampFormat::default(), ByteUnit::Binary);
+        assert!(entry.is_directory && !entry.is_file);
+        assert_eq!(entry.category, FileCategory::Other);
+    }
+
+    #[test]
+    fn failed_metadata_yields_an_empty_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("gone.txt");
+        fs::write(&file, "data").unwrap();
+
+        let failed = Some(Err(io::Error::from(io::ErrorKind::PermissionDenied)));
+        let entry =
+            walked(&file, failed).to_file_entry(TimestampFormat::default(), ByteUnit::Binary);
+        assert_eq!(entry.size, 0);
+        assert_eq!(entry.extension.as_deref(), Some("txt"));
+        assert!(entry.modified.is_none() && entry.created.is_none());
+
+        fs::remove_file(&file).unwrap();
+        let missing = FileEntry::from_path(&file, ByteUnit::Binary).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parallel_walk_stats_the_root_and_marks_it_visited() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("file.txt"), "abc").unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("loop")).unwrap();
+
+        let options = WalkOptions {
+            max_depth: usize::MAX,
+            follow_symlinks: true,
+            with_metadata: true,
+        };
+        let session = Arc::new(ScanSession::new(String::new()));
+        let filter = filter(&dir.path().to_string_lossy());
+        let items: Vec<WalkItem> = walk_parallel(filter, options, 2, session).collect();
+
+        let root = items.iter().find_map(|item| match item {
+            WalkItem::Entry(entry) if entry.depth == 0 => Some(entry),
+            _ => None,
+        });
+        let metadata = root.unwrap().metadata.as_ref().unwrap().as_ref().unwrap();
+        assert!(metadata.is_dir() && metadata.modified().is_ok());
+
+        // The link back to the root is reported instead of walked again
+        let cycles: Vec<&ScanErrorEntry> = items
+            .iter()
+            .filter_map(|item| match item {
+                WalkItem::Error(error) => Some(error),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cycles.len(), 1);
+        assert!(cycles[0].path.ends_with("loop"));
+    }
+}
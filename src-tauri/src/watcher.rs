@@ -0,0 +1,142 @@
+// VELOX CORE - Filesystem Watching
+// Live change notifications with short-window coalescing
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::error::{VeloxError, VeloxResult};
+use crate::types::{FsEvent, FsEventKind};
+
+/// Changes to the same path within this window are merged into one event
+const COALESCE_WINDOW: Duration = Duration::from_millis(200);
+
+/// Running watch; dropping it stops the OS watcher and its coalescing thread
+pub struct DirectoryWatch {
+    _watcher: RecommendedWatcher,
+}
+
+/// Change waiting for its coalescing window to close
+struct PendingChange {
+    kind: FsEventKind,
+    first_seen: Instant,
+    timestamp: DateTime<Utc>,
+}
+
+fn watch_error(error: notify::Error) -> VeloxError {
+    VeloxError::Watch(error.to_string())
+}
+
+/// Start watching `root` recursively, calling `on_event` for every coalesced change
+pub fn start_watch<F>(watch_id: String, root: &Path, on_event: F) -> VeloxResult<DirectoryWatch>
+where
+    F: Fn(FsEvent) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+    watcher
+        .watch(root, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+
+    thread::spawn(move || coalesce(watch_id, rx, on_event));
+
+    Ok(DirectoryWatch { _watcher: watcher })
+}
+
+/// Map a raw notify event to per-path changes; access and unknown events are ignored
+fn classify(event: Event) -> Vec<(PathBuf, FsEventKind)> {
+    let kind = match event.kind {
+        EventKind::Create(_) => FsEventKind::Created,
+        EventKind::Remove(_) => FsEventKind::Removed,
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => FsEventKind::Removed,
+        EventKind::Modify(ModifyKind::Name(RenameMode::To)) => FsEventKind::Created,
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+            // Paths are reported as [from, to]
+            let mut paths = event.paths.into_iter();
+            return paths
+                .next()
+                .map(|from| (from, FsEventKind::Removed))
+                .into_iter()
+                .chain(paths.next().map(|to| (to, FsEventKind::Created)))
+                .collect();
+        }
+        EventKind::Modify(_) => FsEventKind::Modified,
+        _ => return Vec::new(),
+    };
+
+    event.paths.into_iter().map(|path| (path, kind)).collect()
+}
+
+/// Fold a new change into whatever is already pending for the path
+fn merge(pending: &mut HashMap<PathBuf, PendingChange>, path: PathBuf, kind: FsEventKind) {
+    let Some(existing) = pending.get_mut(&path) else {
+        pending.insert(
+            path,
+            PendingChange {
+                kind,
+                first_seen: Instant::now(),
+                timestamp: Utc::now(),
+            },
+        );
+        return;
+    };
+
+    match (existing.kind, kind) {
+        // Writes right after creation are part of creating the file
+        (FsEventKind::Created, FsEventKind::Modified) => {}
+        // Temp files that came and went inside the window are never reported
+        (FsEventKind::Created, FsEventKind::Removed) => {
+            pending.remove(&path);
+        }
+        // Delete-and-recreate, as done by editors saving atomically
+        (FsEventKind::Removed, FsEventKind::Created) => existing.kind = FsEventKind::Modified,
+        (_, kind) => existing.kind = kind,
+    }
+}
+
+/// Collect raw events and emit each path once its coalescing window has passed
+fn coalesce<F>(watch_id: String, rx: Receiver<notify::Result<Event>>, on_event: F)
+where
+    F: Fn(FsEvent),
+{
+    let mut pending: HashMap<PathBuf, PendingChange> = HashMap::new();
+
+    loop {
+        match rx.recv_timeout(COALESCE_WINDOW) {
+            Ok(Ok(event)) => {
+                for (path, kind) in classify(event) {
+                    merge(&mut pending, path, kind);
+                }
+            }
+            Ok(Err(e)) => tracing::warn!("⚠️ Watch error for {}: {}", watch_id, e),
+            Err(RecvTimeoutError::Timeout) => {}
+            // The watcher was dropped by stop_watch
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, change)| change.first_seen.elapsed() >= COALESCE_WINDOW)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        for path in ready {
+            if let Some(change) = pending.remove(&path) {
+                on_event(FsEvent {
+                    watch_id: watch_id.clone(),
+                    kind: change.kind,
+                    path: path.to_string_lossy().to_string(),
+                    timestamp: change.timestamp.to_rfc3339(),
+                });
+            }
+        }
+    }
+
+    tracing::debug!("Watch stopped: {}", watch_id);
+}
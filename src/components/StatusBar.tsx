@@ -80,12 +80,16 @@ export function StatusBar() {
               scanStatus === 'completed' && 'text-accent-success',
               scanStatus === 'scanning' && 'text-accent-primary',
               scanStatus === 'error' && 'text-accent-danger',
-              scanStatus === 'cancelled' && 'text-accent-warning'
+              scanStatus === 'cancelled' && 'text-accent-warning',
+              scanStatus === 'truncated' && 'text-accent-warning',
+              scanStatus === 'memory_limit_exceeded' && 'text-accent-danger'
             )}>
               {scanStatus === 'scanning' && 'Scanning...'}
               {scanStatus === 'completed' && 'Scan Complete'}
               {scanStatus === 'error' && 'Scan Error'}
               {scanStatus === 'cancelled' && 'Scan Cancelled'}
+              {scanStatus === 'truncated' && 'Scan Truncated'}
+              {scanStatus === 'memory_limit_exceeded' && 'Memory Limit Reached'}
             </span>
           </div>
         )}
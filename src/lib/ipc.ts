@@ -4,12 +4,43 @@
 import { invoke } from '@tauri-apps/api/tauri';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import type {
+  AgeBucket,
+  CategorySummary,
+  DiskUsage,
+  DuplicateReport,
+  EntryFilterSpec,
+  EntryPage,
+  ExportStats,
+  ExtensionStat,
+  FileEntry,
+  FsEvent,
+  HashAlgorithm,
+  NdjsonScanSummary,
+  QueryParams,
   ScanRequest,
   ScanResult,
   ScanProgress,
+  ScanBatch,
+  ScanDiff,
+  ScanEstimate,
+  ScanPhaseEvent,
+  ScanProfile,
+  ScanSnapshot,
   ScanStatus,
+  ScanSummary,
+  SimilarGroup,
+  SizeSummary,
   SystemInfo,
+  TextPreview,
+  TrashResult,
+  TreeNode,
+  ValidationReport,
   HeartbeatResponse,
+  LogLine,
+  MatchCount,
+  VeloxConfig,
+  VerifyReport,
+  VolumeInfo,
   ReadyEvent,
   ScanErrorEvent,
 } from '@/types';
@@ -25,6 +56,142 @@ export async function scanDirectory(request: ScanRequest): Promise<ScanResult> {
   return invoke<ScanResult>('scan_directory', { request });
 }
 
+/**
+ * Scan straight into an NDJSON file, one entry per line, without sending
+ * entries over IPC. Progress events are emitted as for scanDirectory.
+ */
+export async function scanToNdjsonFile(
+  request: ScanRequest,
+  outputPath: string
+): Promise<NdjsonScanSummary> {
+  return invoke<NdjsonScanSummary>('scan_to_ndjson_file', { request, outputPath });
+}
+
+/**
+ * Continue an interrupted scan from its checkpoint file. Totals cover the
+ * whole scan; entries only cover what was walked after the resume.
+ */
+export async function resumeScanFromCheckpoint(checkpointPath: string): Promise<ScanResult> {
+  return invoke<ScanResult>('resume_scan_from_checkpoint', { checkpointPath });
+}
+
+/**
+ * Check a scan request without starting it
+ */
+export async function validateScanRequest(request: ScanRequest): Promise<ValidationReport> {
+  return invoke<ValidationReport>('validate_scan_request', { request });
+}
+
+/**
+ * Predict how long a scan would take from a short (~500ms) sample.
+ * Can be stopped with cancelScan like any other scan.
+ */
+export async function estimateScan(request: ScanRequest): Promise<ScanEstimate> {
+  return invoke<ScanEstimate>('estimate_scan', { request });
+}
+
+/**
+ * Scan a directory and group files with identical content
+ */
+export async function findDuplicates(request: ScanRequest): Promise<DuplicateReport> {
+  return invoke<DuplicateReport>('find_duplicates', { request });
+}
+
+/**
+ * Scan a directory and group visually similar images. `threshold` is the
+ * largest perceptual hash distance (in bits) that still counts as similar.
+ */
+export async function findSimilarImages(
+  request: ScanRequest,
+  threshold: number
+): Promise<SimilarGroup[]> {
+  return invoke<SimilarGroup[]>('find_similar_images', { request, threshold });
+}
+
+/**
+ * Scan a directory and return its largest files, biggest first
+ */
+export async function findLargestFiles(request: ScanRequest, limit: number): Promise<FileEntry[]> {
+  return invoke<FileEntry[]>('find_largest_files', { request, limit });
+}
+
+/**
+ * Scan a directory and return the folders below it with the largest
+ * recursive sizes, biggest first
+ */
+export async function findLargestDirectories(
+  request: ScanRequest,
+  limit: number
+): Promise<FileEntry[]> {
+  return invoke<FileEntry[]>('find_largest_directories', { request, limit });
+}
+
+/**
+ * Files modified within the last `withinHours`, newest first
+ */
+export async function findRecentlyModified(
+  path: string,
+  withinHours: number,
+  limit: number
+): Promise<FileEntry[]> {
+  return invoke<FileEntry[]>('find_recently_modified', { path, withinHours, limit });
+}
+
+/**
+ * Scan a directory and break down disk usage by extension, largest first
+ */
+export async function analyzeByExtension(request: ScanRequest): Promise<ExtensionStat[]> {
+  return invoke<ExtensionStat[]>('analyze_by_extension', { request });
+}
+
+/**
+ * Scan a directory and break down disk usage by file category, largest first.
+ * Only regular files are counted; there is never a directory row.
+ */
+export async function analyzeByCategory(request: ScanRequest): Promise<CategorySummary[]> {
+  return invoke<CategorySummary[]>('analyze_by_category', { request });
+}
+
+/**
+ * Scan a directory and bucket its files by last-modified age, youngest first
+ */
+export async function analyzeFileAges(request: ScanRequest): Promise<AgeBucket[]> {
+  return invoke<AgeBucket[]>('analyze_file_ages', { request });
+}
+
+/**
+ * Total up a folder without collecting entries. Progress events are emitted
+ * as for scanDirectory, so the same listeners work.
+ */
+export async function computeDirectorySize(request: ScanRequest): Promise<SizeSummary> {
+  return invoke<SizeSummary>('compute_directory_size', { request });
+}
+
+/**
+ * Count files, directories and bytes matching a filter without collecting
+ * entries. Fields set on the filter override the request's own filters.
+ */
+export async function countMatching(
+  request: ScanRequest,
+  filter: EntryFilterSpec
+): Promise<MatchCount> {
+  return invoke<MatchCount>('count_matching', { request, filter });
+}
+
+/**
+ * Recompute SHA-256 digests for the files listed in a `path,sha256` manifest
+ */
+export async function verifyChecksums(manifestPath: string): Promise<VerifyReport> {
+  return invoke<VerifyReport>('verify_checksums', { manifestPath });
+}
+
+/**
+ * Hash a single file; resolves to the hex digest
+ */
+export async function hashFile(path: string, algorithm: HashAlgorithm): Promise<string> {
+  return invoke<string>('hash_file', { path, algorithm });
+}
+
 /**
  * Cancel an active scan
  */
@@ -32,6 +199,27 @@ export async function cancelScan(scanId: string): Promise<boolean> {
   return invoke<boolean>('cancel_scan', { scanId });
 }
 
+/**
+ * Cancel every active scan, returning how many were cancelled
+ */
+export async function cancelAllScans(): Promise<number> {
+  return invoke<number>('cancel_all_scans');
+}
+
+/**
+ * Pause an active scan
+ */
+export async function pauseScan(scanId: string): Promise<boolean> {
+  return invoke<boolean>('pause_scan', { scanId });
+}
+
+/**
+ * Resume a paused scan
+ */
+export async function resumeScan(scanId: string): Promise<boolean> {
+  return invoke<boolean>('resume_scan', { scanId });
+}
+
 /**
  * Get the status of an active scan
  */
@@ -39,6 +227,184 @@ export async function getScanStatus(scanId: string): Promise<ScanStatus> {
   return invoke<ScanStatus>('get_scan_status', { scanId });
 }
 
+/**
+ * List in-flight scans with live progress snapshots
+ */
+export async function listActiveScans(): Promise<ScanSnapshot[]> {
+  return invoke<ScanSnapshot[]>('list_active_scans');
+}
+
+/**
+ * Get metadata for a single file or directory without scanning
+ */
+export async function getFileInfo(path: string): Promise<FileEntry> {
+  return invoke<FileEntry>('get_file_info', { path });
+}
+
+/**
+ * Read the start of a text file for an inline preview. maxBytes is capped
+ * at 1 MiB by the backend; binary files come back with empty content.
+ */
+export async function readTextPreview(path: string, maxBytes: number): Promise<TextPreview> {
+  return invoke<TextPreview>('read_text_preview', { path, maxBytes });
+}
+
+/**
+ * Show a file or folder selected in the system file manager
+ */
+export async function revealInFileManager(path: string): Promise<void> {
+  return invoke<void>('reveal_in_file_manager', { path });
+}
+
+/**
+ * Open a file or folder with the system default application
+ */
+export async function openPath(path: string): Promise<void> {
+  return invoke<void>('open_path', { path });
+}
+
+/**
+ * Move paths to the system trash, reporting which ones succeeded
+ */
+export async function moveToTrash(paths: string[]): Promise<TrashResult> {
+  return invoke<TrashResult>('move_to_trash', { paths });
+}
+
+/**
+ * Move or rename a file or folder, copying across volumes when needed.
+ * An existing destination is only replaced when overwrite is true.
+ */
+export async function movePath(from: string, to: string, overwrite = false): Promise<void> {
+  return invoke<void>('move_path', { from, to, overwrite });
+}
+
+/**
+ * Put text such as a scanned path on the system clipboard
+ */
+export async function copyToClipboard(text: string): Promise<void> {
+  return invoke<void>('copy_to_clipboard', { text });
+}
+
+/**
+ * Watch a directory for changes, returning the watch ID
+ */
+export async function watchDirectory(path: string): Promise<string> {
+  return invoke<string>('watch_directory', { path });
+}
+
+/**
+ * Stop a running directory watch
+ */
+export async function stopWatch(watchId: string): Promise<boolean> {
+  return invoke<boolean>('stop_watch', { watchId });
+}
+
+/**
+ * Get summaries of recently finished scans, newest first
+ */
+export async function getScanHistory(): Promise<ScanSummary[]> {
+  return invoke<ScanSummary[]>('get_scan_history');
+}
+
+/**
+ * Fetch a retained scan result again, e.g. after a UI reload
+ */
+export async function getScanResult(scanId: string): Promise<ScanResult> {
+  return invoke<ScanResult>('get_scan_result', { scanId });
+}
+
+/**
+ * Filter, sort and page through a retained scan's entries
+ */
+export async function queryScanEntries(scanId: string, params: QueryParams): Promise<EntryPage> {
+  return invoke<EntryPage>('query_scan_entries', { scanId, params });
+}
+
+/**
+ * Scan a directory keeping only entries that match `params`, and return the
+ * first page. Fetch further pages with `queryScanEntries(page.scanId, ...)`.
+ */
+export async function scanWithQuery(request: ScanRequest, params: QueryParams): Promise<EntryPage> {
+  return invoke<EntryPage>('scan_with_query', { request, params });
+}
+
+/**
+ * Nest a retained scan's entries into one tree per scanned root
+ */
+export async function getScanTree(scanId: string, maxDepth?: number): Promise<TreeNode[]> {
+  return invoke<TreeNode[]>('get_scan_tree', { scanId, maxDepth });
+}
+
+/**
+ * Compare two retained scans of the same tree
+ */
+export async function diffScans(baseScanId: string, newScanId: string): Promise<ScanDiff> {
+  return invoke<ScanDiff>('diff_scans', { baseScanId, newScanId });
+}
+
+/**
+ * Walk one folder of a retained scan again and merge the fresh entries into it
+ */
+export async function rescanSubtree(scanId: string, subtreePath: string): Promise<ScanResult> {
+  return invoke<ScanResult>('rescan_subtree', { scanId, subtreePath });
+}
+
+/**
+ * Drop all retained scan results, returning how many were cleared
+ */
+export async function clearScanResults(): Promise<number> {
+  return invoke<number>('clear_scan_results');
+}
+
+/**
+ * Export a completed scan to CSV. Gzipped when `compress` is set, or when it's
+ * omitted and the path ends in `.gz`.
+ */
+export async function exportScanCsv(
+  scanId: string,
+  outputPath: string,
+  compress?: boolean
+): Promise<ExportStats> {
+  return invoke<ExportStats>('export_scan_csv', { scanId, outputPath, compress });
+}
+
+/**
+ * Export a completed scan as newline-delimited JSON, compressed like `exportScanCsv`
+ */
+export async function exportScanNdjson(
+  scanId: string,
+  outputPath: string,
+  compress?: boolean
+): Promise<ExportStats> {
+  return invoke<ExportStats>('export_scan_ndjson', { scanId, outputPath, compress });
+}
+
+/**
+ * Export a completed scan as a self-contained HTML report, returning its path
+ */
+export async function exportScanHtml(scanId: string, outputPath: string): Promise<string> {
+  return invoke<string>('export_scan_html', { scanId, outputPath });
+}
+
+/**
+ * Export a completed scan as an indented Markdown tree, directories first
+ */
+export async function exportScanTreeMarkdown(
+  scanId: string,
+  outputPath: string,
+  maxDepth?: number
+): Promise<ExportStats> {
+  return invoke<ExportStats>('export_scan_tree_markdown', { scanId, outputPath, maxDepth });
+}
+
+/**
+ * Export a completed scan into a SQLite database with a `files` table,
+ * returning the number of rows written
+ */
+export async function exportScanSqlite(scanId: string, dbPath: string): Promise<number> {
+  return invoke<number>('export_scan_sqlite', { scanId, dbPath });
+}
+
 /**
  * Get system information
  */
@@ -46,6 +412,20 @@ export async function getSystemInfo(): Promise<SystemInfo> {
   return invoke<SystemInfo>('get_system_info');
 }
 
+/**
+ * List mounted volumes with their capacity and free space
+ */
+export async function listVolumes(): Promise<VolumeInfo[]> {
+  return invoke<VolumeInfo[]>('list_volumes');
+}
+
+/**
+ * Capacity and free space of the volume containing a path
+ */
+export async function getPathDiskUsage(path: string): Promise<DiskUsage> {
+  return invoke<DiskUsage>('get_path_disk_usage', { path });
+}
+
 /**
  * Heartbeat for frontend-backend sync verification
  */
@@ -53,6 +433,63 @@ export async function heartbeat(): Promise<HeartbeatResponse> {
   return invoke<HeartbeatResponse>('heartbeat');
 }
 
+/**
+ * The last `limit` captured backend log lines, oldest first
+ */
+export async function getRecentLogs(limit: number): Promise<LogLine[]> {
+  return invoke<LogLine[]>('get_recent_logs', { limit });
+}
+
+/**
+ * JSON Schemas for ScanRequest, ScanResult, ScanProgress, FileEntry and
+ * ErrorResponse, keyed by type name
+ */
+export async function getTypeSchemas(): Promise<Record<string, unknown>> {
+  return invoke<Record<string, unknown>>('get_type_schemas');
+}
+
+/**
+ * Get the current application configuration
+ */
+export async function getConfig(): Promise<VeloxConfig> {
+  return invoke<VeloxConfig>('get_config');
+}
+
+/**
+ * Replace the application configuration and persist it to disk
+ */
+export async function updateConfig(config: VeloxConfig): Promise<VeloxConfig> {
+  return invoke<VeloxConfig>('update_config', { config });
+}
+
+/**
+ * Save a scan request under a new name; names are unique ignoring case
+ */
+export async function saveProfile(name: string, request: ScanRequest): Promise<ScanProfile> {
+  return invoke<ScanProfile>('save_profile', { name, request });
+}
+
+/**
+ * List saved scan profiles, sorted by name
+ */
+export async function listProfiles(): Promise<ScanProfile[]> {
+  return invoke<ScanProfile[]>('list_profiles');
+}
+
+/**
+ * Delete a saved scan profile
+ */
+export async function deleteProfile(name: string): Promise<boolean> {
+  return invoke<boolean>('delete_profile', { name });
+}
+
+/**
+ * Run a saved profile's scan. Progress events are emitted as for scanDirectory.
+ */
+export async function runProfile(name: string): Promise<ScanResult> {
+  return invoke<ScanResult>('run_profile', { name });
+}
+
 /**
  * Open native folder dialog and return selected path
  */
@@ -78,6 +515,20 @@ export async function onScanProgress(callback: (progress: ScanProgress) => void)
   return listen<ScanProgress>('velox:scan:progress', (event) => callback(event.payload));
 }
 
+/**
+ * Listen for scan phase transitions (counting, walking, hashing, aggregating)
+ */
+export async function onScanPhase(callback: (event: ScanPhaseEvent) => void): Promise<UnlistenFn> {
+  return listen<ScanPhaseEvent>('velox:scan:phase', (event) => callback(event.payload));
+}
+
+/**
+ * Listen for streamed entry batches
+ */
+export async function onScanBatch(callback: (batch: ScanBatch) => void): Promise<UnlistenFn> {
+  return listen<ScanBatch>('velox:scan:batch', (event) => callback(event.payload));
+}
+
 /**
  * Listen for scan completion events
  */
@@ -92,6 +543,13 @@ export async function onScanError(callback: (error: ScanErrorEvent) => void): Pr
   return listen<ScanErrorEvent>('velox:scan:error', (event) => callback(event.payload));
 }
 
+/**
+ * Listen for filesystem changes from active watches
+ */
+export async function onFsEvent(callback: (event: FsEvent) => void): Promise<UnlistenFn> {
+  return listen<FsEvent>('velox:fs:event', (event) => callback(event.payload));
+}
+
 // ============================================================================
 // HEARTBEAT MONITOR
 // ============================================================================
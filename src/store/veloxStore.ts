@@ -122,12 +122,14 @@ export const useVeloxStore = create<VeloxState>()(
           path,
           includeHidden: false,
           followSymlinks: false,
+          // The explorer keys and selects entries by id
+          generateIds: true,
         });
 
         set({
           scanResult: result,
           currentScanId: result.scanId,
-          scanStatus: 'completed',
+          scanStatus: result.status,
           filteredEntries: result.entries,
         });
       } catch (error) {
@@ -162,7 +164,7 @@ export const useVeloxStore = create<VeloxState>()(
     completeScan: (result) => {
       set({
         scanResult: result,
-        scanStatus: 'completed',
+        scanStatus: result.status,
         filteredEntries: result.entries,
       });
     },
@@ -251,8 +253,8 @@ function sortEntries(
         comparison = a.size - b.size;
         break;
       case 'modified':
-        const aTime = a.modified ? new Date(a.modified).getTime() : 0;
-        const bTime = b.modified ? new Date(b.modified).getTime() : 0;
+        const aTime = a.modifiedMs ?? (a.modified ? new Date(a.modified).getTime() : 0);
+        const bTime = b.modifiedMs ?? (b.modified ? new Date(b.modified).getTime() : 0);
         comparison = aTime - bTime;
         break;
       case 'type':
@@ -3,6 +3,7 @@
 
 /** File entry metadata - mirrors Rust FileEntry */
 export interface FileEntry {
+  /** Empty unless the scan request set `generateIds` */
   id: string;
   name: string;
   path: string;
@@ -11,43 +12,426 @@ export interface FileEntry {
   isDirectory: boolean;
   isFile: boolean;
   isSymlink: boolean;
+  /** Without the dot; compound forms such as 'tar.gz' are kept whole */
   extension: string | null;
   modified: string | null;
   created: string | null;
+  /** Last access time; null on noatime mounts and only approximate with relatime */
+  accessed: string | null;
+  /** Epoch millis, set instead of the string timestamps for timestampFormat 'unix_millis' */
+  modifiedMs: number | null;
+  createdMs: number | null;
+  accessedMs: number | null;
   depth: number;
   childrenCount: number | null;
+  /** Hex digest; the algorithm is recorded on the enclosing ScanResult */
+  hash: string | null;
+  mimeType: string | null;
+  /** Coarse type derived from the extension; directories are 'other' */
+  category: FileCategory;
+  /** Unix mode bits; this and the fields below need readPermissions and stay null on Windows */
+  permissions: number | null;
+  /** ls-style rendering of permissions, e.g. 'rwxr-xr-x' */
+  permissionsString: string | null;
+  ownerUid: number | null;
+  ownerGid: number | null;
+  /** Fewer bytes allocated than the logical size; needs detectSparse, null on Windows */
+  isSparse: boolean | null;
+  /** Symlink whose target is missing; null for other entries or without metadata */
+  isBroken: boolean | null;
 }
 
+/** Broad file type for grouping and icons - mirrors Rust FileCategory */
+export type FileCategory =
+  | 'image'
+  | 'video'
+  | 'audio'
+  | 'document'
+  | 'archive'
+  | 'code'
+  | 'executable'
+  | 'other';
+
+/** Path that could not be fully read - mirrors Rust ScanErrorEntry */
+export interface ScanErrorEntry {
+  path: string;
+  message: string;
+  kind: ScanErrorKind;
+}
+
+/** Error classification - `access_denied` marks genuinely inaccessible paths */
+export type ScanErrorKind = 'access_denied' | 'not_found' | 'io' | 'symlink_cycle' | 'hash_failed';
+
 /** Directory scan result - mirrors Rust ScanResult */
 export interface ScanResult {
   scanId: string;
   rootPath: string;
+  roots: RootSummary[];
   totalFiles: number;
   totalDirectories: number;
   totalSize: number;
   totalSizeFormatted: string;
   entries: FileEntry[];
+  errors: ScanErrorEntry[];
+  extensionStats: ExtensionStat[] | null;
+  ageBuckets: AgeBucket[] | null;
+  /** Counted entries per depth, shallowest first */
+  depthDistribution: DepthBucket[];
+  categoryStats: CategorySummary[] | null;
+  durationMs: number;
+  completedAt: string;
+  /** True when the scan stopped early at `maxEntries` */
+  truncated: boolean;
+  /** Entries skipped because their symlink target was already counted */
+  symlinkDuplicatesSkipped: number;
+  /** Directories not descended into because skipSystemDirs matched their name */
+  prunedDirectories: number;
+  /** Algorithm behind `FileEntry.hash`; null when files weren't hashed */
+  hashAlgorithm: HashAlgorithm | null;
+  status: ScanStatus;
+}
+
+/** Totals contributed by one root of a scan - mirrors Rust RootSummary */
+export interface RootSummary {
+  path: string;
+  totalFiles: number;
+  totalDirectories: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+}
+
+/** Outcome of a scan streamed into an NDJSON file - mirrors Rust NdjsonScanSummary */
+export interface NdjsonScanSummary {
+  scanId: string;
+  outputPath: string;
+  totalFiles: number;
+  totalDirectories: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+  linesWritten: number;
+  bytesWritten: number;
+  durationMs: number;
+  status: ScanStatus;
+}
+
+/** Totals of a size-only scan - mirrors Rust SizeSummary */
+export interface SizeSummary {
+  totalFiles: number;
+  totalDirectories: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+}
+
+/** Filters for countMatching - mirrors Rust EntryFilterSpec */
+export interface EntryFilterSpec {
+  includeExtensions?: string[];
+  excludeExtensions?: string[];
+  minSize?: number;
+  maxSize?: number;
+  nameRegex?: string;
+  nameContains?: string;
+}
+
+/** Entries matching a filter spec - mirrors Rust MatchCount */
+export interface MatchCount {
+  files: number;
+  /** Directories walked; name filters only narrow these with filterDirectoryNames */
+  directories: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+}
+
+/** Condensed record of a finished scan - mirrors Rust ScanSummary */
+export interface ScanSummary {
+  scanId: string;
+  rootPath: string;
+  totalFiles: number;
+  totalDirectories: number;
+  totalSize: number;
+  totalSizeFormatted: string;
   durationMs: number;
   completedAt: string;
   status: ScanStatus;
 }
 
+/**
+ * Per-entry metadata a scan reads - mirrors Rust MetadataLevel.
+ * `none` skips stat calls (sizes 0, no timestamps); `basic` keeps sizes only.
+ */
+export type MetadataLevel = 'none' | 'basic' | 'full';
+
+/** FileEntry timestamp representation - mirrors Rust TimestampFormat */
+export type TimestampFormat = 'rfc3339' | 'unix_millis';
+
+/** Decimal (KB) or binary (KiB) formatted sizes - mirrors Rust ByteUnit */
+export type ByteUnit = 'decimal' | 'binary';
+
+/**
+ * Kinds of entries a scan returns - mirrors Rust EntryKindFilter.
+ * The whole tree is still walked and totals always cover it.
+ */
+export type EntryKindFilter = 'all' | 'files_only' | 'directories_only';
+
+/** Coarse stage of a running scan - mirrors Rust ScanPhase */
+export type ScanPhase = 'counting' | 'walking' | 'hashing' | 'aggregating';
+
+/** Phase transition event payload - mirrors Rust ScanPhaseEvent */
+export interface ScanPhaseEvent {
+  scanId: string;
+  phase: ScanPhase;
+  /** Extra context such as the root being walked */
+  detail: string | null;
+}
+
+/** Streamed entry batch event payload - mirrors Rust ScanBatch */
+export interface ScanBatch {
+  scanId: string;
+  batchIndex: number;
+  entries: FileEntry[];
+}
+
+/** Set of files sharing identical content - mirrors Rust DuplicateGroup */
+export interface DuplicateGroup {
+  hash: string;
+  size: number;
+  sizeFormatted: string;
+  paths: string[];
+}
+
+/** Duplicate detection report - mirrors Rust DuplicateReport */
+export interface DuplicateReport {
+  groups: DuplicateGroup[];
+}
+
+/** Image within a group of similar images - mirrors Rust SimilarImage */
+export interface SimilarImage {
+  path: string;
+  size: number;
+  /** Hamming distance from the group's first image; 0 for that image */
+  distance: number;
+}
+
+/** Images whose perceptual hashes are close - mirrors Rust SimilarGroup */
+export interface SimilarGroup {
+  /** Base64 perceptual hash of the group's first image */
+  hash: string;
+  /** Closest first */
+  images: SimilarImage[];
+}
+
+/** One problem found while validating a request - mirrors Rust ValidationIssue */
+export interface ValidationIssue {
+  /** Request field the issue belongs to */
+  field: string;
+  message: string;
+}
+
+/** Outcome of a dry-run request check - mirrors Rust ValidationReport */
+export interface ValidationReport {
+  /** True when the scan would start; warnings don't affect this */
+  valid: boolean;
+  errors: ValidationIssue[];
+  warnings: ValidationIssue[];
+  /** Order-of-magnitude entry count from a shallow sample */
+  estimatedEntries: number | null;
+}
+
+/** How much of the tree an estimate looked at - mirrors Rust EstimateConfidence */
+export type EstimateConfidence = 'low' | 'medium' | 'high';
+
+/** Predicted size and duration of a scan - mirrors Rust ScanEstimate */
+export interface ScanEstimate {
+  estimatedFiles: number;
+  estimatedDirectories: number;
+  estimatedDurationMs: number;
+  confidence: EstimateConfidence;
+  /** Entries actually listed while sampling */
+  sampledEntries: number;
+  sampleMs: number;
+}
+
+/** Differences between two scans of the same tree - mirrors Rust ScanDiff */
+export interface ScanDiff {
+  added: FileEntry[];
+  removed: string[];
+  /** Files whose size or modified time changed; directories never appear here */
+  modified: FileEntry[];
+  /** New total size minus base total size */
+  sizeDelta: number;
+}
+
+/** Entry field a query can sort by - mirrors Rust EntryField */
+export type EntryField = 'path' | 'name' | 'size' | 'modified';
+
+/** Filter, sort and page window over stored entries - mirrors Rust QueryParams */
+export interface QueryParams {
+  offset?: number;
+  /** Defaults to 100 */
+  limit?: number;
+  sortBy?: EntryField;
+  descending?: boolean;
+  /** Case-insensitive substring the entry name must contain */
+  nameContains?: string;
+}
+
+/** One page of query results - mirrors Rust EntryPage */
+export interface EntryPage {
+  /** Retained scan the page was taken from, for requesting further pages */
+  scanId: string;
+  entries: FileEntry[];
+  totalMatched: number;
+}
+
+/** An entry with its children nested below it - mirrors Rust TreeNode */
+export interface TreeNode {
+  entry: FileEntry;
+  children: TreeNode[];
+  /** Children left out because the node sits at the depth cap */
+  omittedChildren: number;
+}
+
+/** Disk usage for one file extension - mirrors Rust ExtensionStat */
+export interface ExtensionStat {
+  extension: string | null;
+  fileCount: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+}
+
+/** Digest algorithm for file hashing - mirrors Rust HashAlgorithm */
+export type HashAlgorithm = 'sha256' | 'blake3' | 'md5';
+
+/** Candidate search used by findDuplicates - mirrors Rust DedupMode */
+export type DedupMode = 'exact' | 'approximate';
+
+/** OS scheduling priority of a scan - mirrors Rust ScanPriority */
+export type ScanPriority = 'normal' | 'low';
+
+/** Disk usage for one file category, regular files only - mirrors Rust CategorySummary */
+export interface CategorySummary {
+  category: FileCategory;
+  fileCount: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+}
+
+/** Outcome of checking one manifest entry - mirrors Rust VerifyStatus */
+export type VerifyStatus = 'match' | 'mismatch' | 'missing' | 'unreadable';
+
+/** Manifest line with its recomputed digest - mirrors Rust VerifyEntry */
+export interface VerifyEntry {
+  path: string;
+  expected: string;
+  actual: string | null;
+  status: VerifyStatus;
+  error: string | null;
+}
+
+/** Checksum verification report - mirrors Rust VerifyReport */
+export interface VerifyReport {
+  entries: VerifyEntry[];
+  matched: number;
+  mismatched: number;
+  missing: number;
+  unreadable: number;
+}
+
+/** Entries found at one depth below a root - mirrors Rust DepthBucket */
+export interface DepthBucket {
+  /** 0 is the root itself */
+  depth: number;
+  fileCount: number;
+  dirCount: number;
+}
+
+/** Files in one modified-time range - mirrors Rust AgeBucket */
+export interface AgeBucket {
+  /** `<1d`, `<7d`, `<30d`, `<1y`, `older`, or `unknown` */
+  label: string;
+  fileCount: number;
+  totalSize: number;
+  totalSizeFormatted: string;
+}
+
+/** Path that could not be trashed - mirrors Rust TrashFailure */
+export interface TrashFailure {
+  path: string;
+  message: string;
+}
+
+/** Per-path trash outcome - mirrors Rust TrashResult */
+export interface TrashResult {
+  trashed: string[];
+  errors: TrashFailure[];
+}
+
+/** Line-oriented export outcome - mirrors Rust ExportStats */
+export interface ExportStats {
+  linesWritten: number;
+  /** Size of the output before compression */
+  bytesWritten: number;
+  /** Size on disk when the output was gzip-compressed */
+  compressedBytes: number | null;
+}
+
+/** Head of a file decoded for the inline viewer - mirrors Rust TextPreview */
+export interface TextPreview {
+  /** Empty when isBinary is set */
+  content: string;
+  /** True when the file continues past what was read */
+  truncated: boolean;
+  isBinary: boolean;
+}
+
 /** Scan progress event payload - mirrors Rust ScanProgress */
 export interface ScanProgress {
   scanId: string;
+  /** Increases by one per emitted event; the final event carries the highest value */
+  seq: number;
   currentPath: string;
+  /** Root this update covers when roots are walked concurrently; null for the whole scan */
+  root: string | null;
   filesScanned: number;
   directoriesScanned: number;
   bytesScanned: number;
   bytesScannedFormatted: string;
   progressPercent: number;
   estimatedTotal: number | null;
+  filesPerSec: number;
+  etaMs: number | null;
   elapsedMs: number;
   status: ScanStatus;
 }
 
+/** A scan request saved under a name - mirrors Rust ScanProfile */
+export interface ScanProfile {
+  /** Unique, ignoring case */
+  name: string;
+  request: ScanRequest;
+  savedAt: string;
+}
+
+/** Live view of an in-flight scan - mirrors Rust ScanSnapshot */
+export interface ScanSnapshot {
+  scanId: string;
+  rootPath: string;
+  status: ScanStatus;
+  startedAt: string;
+  filesScanned: number;
+  elapsedMs: number;
+}
+
 /** Scan status enum - mirrors Rust ScanStatus */
-export type ScanStatus = 'idle' | 'scanning' | 'completed' | 'cancelled' | 'error';
+export type ScanStatus =
+  | 'idle'
+  | 'counting'
+  | 'scanning'
+  | 'paused'
+  | 'completed'
+  | 'truncated'
+  | 'cancelled'
+  | 'memory_limit_exceeded'
+  | 'error';
 
 /** System information - mirrors Rust SystemInfo */
 export interface SystemInfo {
@@ -56,9 +440,32 @@ export interface SystemInfo {
   version: string;
   hostname: string;
   cpuCores: number;
+  totalMemoryBytes: number;
+  availableMemoryBytes: number;
+  /** 0 on the first call; measured between consecutive calls */
+  cpuUsagePercent: number;
   timestamp: string;
 }
 
+/** Mounted volume - mirrors Rust VolumeInfo */
+export interface VolumeInfo {
+  name: string;
+  mountPoint: string;
+  totalBytes: number;
+  availableBytes: number;
+  fileSystem: string;
+  isRemovable: boolean;
+}
+
+/** Capacity of the volume holding a path - mirrors Rust DiskUsage */
+export interface DiskUsage {
+  mountPoint: string;
+  totalBytes: number;
+  /** Currently the same figure as `availableBytes` */
+  freeBytes: number;
+  availableBytes: number;
+}
+
 /** Heartbeat response - mirrors Rust HeartbeatResponse */
 export interface HeartbeatResponse {
   status: string;
@@ -66,20 +473,122 @@ export interface HeartbeatResponse {
   activeScans: number;
   timestamp: string;
   version: string;
+  /** Scans registered since startup */
+  totalScansStarted: number;
+  /** Scans that ended without being cancelled, including failed ones */
+  totalScansCompleted: number;
+  totalScansCancelled: number;
+  /** Most scans that were active at the same time */
+  peakActiveScans: number;
+}
+
+/** Severity of a captured log line - mirrors Rust LogLevel */
+export type LogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error';
+
+/** One captured log event - mirrors Rust LogLine */
+export interface LogLine {
+  timestamp: string;
+  level: LogLevel;
+  /** Module that logged the event, e.g. 'velox_core::scanner' */
+  target: string;
+  message: string;
 }
 
 /** Scan request - mirrors Rust ScanRequest */
 export interface ScanRequest {
-  path: string;
+  /** Single root; ignored when `paths` is non-empty */
+  path?: string;
+  /** Several roots combined into one result, scanned in order unless `concurrentRoots` */
+  paths?: string[];
+  /** Walk the roots in `paths` at the same time, up to `threads` at once */
+  concurrentRoots?: boolean;
   maxDepth?: number;
   includeHidden: boolean;
   followSymlinks: boolean;
+  /** With followSymlinks, count each canonical target only once */
+  dedupeSymlinkTargets?: boolean;
+  includeGlobs?: string[];
+  excludeGlobs?: string[];
+  estimateTotal?: boolean;
+  streamBatchSize?: number;
+  /** Stop after collecting this many entries; streamed entries count too */
+  maxEntries?: number;
+  /** Defaults to 'all'; totals are unaffected */
+  entryFilter?: EntryKindFilter;
+  /** Defaults to 'full'; lower levels trade sizes/timestamps for speed */
+  metadata?: MetadataLevel;
+  /** Defaults to 'rfc3339' */
+  timestampFormat?: TimestampFormat;
+  /** Fill Unix mode bits and owner IDs; no effect on Windows */
+  readPermissions?: boolean;
+  /** Check regular files for sparseness; no effect on Windows */
+  detectSparse?: boolean;
+  /** Also list dangling symlinks in the result's errors */
+  reportBrokenSymlinks?: boolean;
+  /** Only collect zero-byte regular files; totals still cover everything */
+  onlyEmptyFiles?: boolean;
+  aggregateDirSizes?: boolean;
+  hashFiles?: boolean;
+  hashMaxBytes?: number;
+  /** Defaults to 'sha256'; 'blake3' is considerably faster on large files */
+  hashAlgorithm?: HashAlgorithm;
+  /** Extra attempts for transient IO errors while hashing (default 2) */
+  ioRetries?: number;
+  /** `approximate` pre-walks sizes through a bloom filter to collect fewer files; same groups */
+  dedupMemoryMode?: DedupMode;
+  /** Pace disk reads to roughly this many bytes per second; best with `hashFiles` */
+  maxBytesPerSec?: number;
+  detectMime?: boolean;
+  parallel?: boolean;
+  threads?: number;
+  minSize?: number;
+  maxSize?: number;
+  countFiltered?: boolean;
+  modifiedAfter?: string;
+  modifiedBefore?: string;
+  includeExtensions?: string[];
+  excludeExtensions?: string[];
+  /** Regular expression file names must match */
+  nameRegex?: string;
+  /** Case-insensitive substring file names must contain */
+  nameContains?: string;
+  /** Apply the name filters to directories too, skipping non-matching ones */
+  filterDirectoryNames?: boolean;
+  /** Skip .git, node_modules, target, trash and similar directories */
+  skipSystemDirs?: boolean;
+  /** Directory names skipSystemDirs prunes instead of the defaults; names match ignoring case */
+  skipDirNames?: string[];
+  respectGitignore?: boolean;
+  /** Record finished subtrees here so an interrupted scan can be resumed */
+  checkpointPath?: string;
+  /** Fill `FileEntry.id`; left empty otherwise */
+  generateIds?: boolean;
+  /** 'low' keeps background scans from slowing foreground work */
+  priority?: ScanPriority;
+}
+
+/** Application configuration - mirrors Rust VeloxConfig */
+export interface VeloxConfig {
+  maxConcurrentScans: number;
+  defaultMaxDepth: number;
+  includeHiddenDefault: boolean;
+  followSymlinksDefault: boolean;
+  progressEmitIntervalMs: number;
+  historyLimit: number;
+  resultCacheLimit: number;
+  /** Scans idle for longer than this are cancelled and dropped; 0 disables */
+  scanTimeoutMs: number;
+  /** Scans stop once the app's resident memory passes this many bytes; 0 disables */
+  memoryLimitBytes: number;
+  /** Decimal (KB) or binary (KiB) units for every formatted size */
+  byteUnit: ByteUnit;
 }
 
 /** Error response from Rust backend */
 export interface ErrorResponse {
   code: string;
   message: string;
+  /** For `IO_ERROR`, the offending path when known; otherwise debug context */
   details: string | null;
   timestamp: string;
 }
@@ -96,6 +605,17 @@ export interface ScanErrorEvent {
   error: string;
 }
 
+/** Filesystem change kind - mirrors Rust FsEventKind */
+export type FsEventKind = 'created' | 'modified' | 'removed';
+
+/** Filesystem change event - mirrors Rust FsEvent */
+export interface FsEvent {
+  watchId: string;
+  kind: FsEventKind;
+  path: string;
+  timestamp: string;
+}
+
 // Type guards for runtime safety
 export function isScanProgress(obj: unknown): obj is ScanProgress {
   return (